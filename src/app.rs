@@ -1,8 +1,13 @@
 //! Main application logic and state management.
 
-use crate::config::Theme;
-use crate::hyprland::Monitor;
-use std::path::PathBuf;
+use crate::config::{self, GeneralSettings, Profile, Theme};
+use crate::hyprland::{
+    self, mirror_cycle, snap_to_neighbours, ApplyError, Arrangement, ColorMode, ExportOptions,
+    Monitor, MonitorBackend, Position, Reserved, Resolution, Severity, Vrr, SDR_BRIGHTNESS_RANGE,
+    SDR_SATURATION_RANGE,
+};
+use std::path::{Path, PathBuf};
+use std::time::{Duration, Instant};
 
 /// Confirmation action type.
 #[derive(Clone, Debug, PartialEq)]
@@ -12,6 +17,10 @@ pub enum ConfirmAction {
     Save,
     Reset,
     DeleteProfile(String),
+    OverwriteProfile {
+        name: String,
+        description: Option<String>,
+    },
 }
 
 /// Fields that can be edited.
@@ -21,6 +30,13 @@ pub enum EditField {
     RefreshRate,
     Scale,
     Transform,
+    Mirror,
+    Reserved,
+    Vrr,
+    Bitdepth,
+    ColorMode,
+    SdrBrightness,
+    SdrSaturation,
     Primary,
     Enabled,
 }
@@ -31,7 +47,14 @@ impl EditField {
             EditField::Resolution => EditField::RefreshRate,
             EditField::RefreshRate => EditField::Scale,
             EditField::Scale => EditField::Transform,
-            EditField::Transform => EditField::Primary,
+            EditField::Transform => EditField::Mirror,
+            EditField::Mirror => EditField::Reserved,
+            EditField::Reserved => EditField::Vrr,
+            EditField::Vrr => EditField::Bitdepth,
+            EditField::Bitdepth => EditField::ColorMode,
+            EditField::ColorMode => EditField::SdrBrightness,
+            EditField::SdrBrightness => EditField::SdrSaturation,
+            EditField::SdrSaturation => EditField::Primary,
             EditField::Primary => EditField::Enabled,
             EditField::Enabled => EditField::Resolution,
         }
@@ -42,12 +65,44 @@ impl EditField {
             EditField::RefreshRate => EditField::Resolution,
             EditField::Scale => EditField::RefreshRate,
             EditField::Transform => EditField::Scale,
-            EditField::Primary => EditField::Transform,
+            EditField::Mirror => EditField::Transform,
+            EditField::Reserved => EditField::Mirror,
+            EditField::Vrr => EditField::Reserved,
+            EditField::Bitdepth => EditField::Vrr,
+            EditField::ColorMode => EditField::Bitdepth,
+            EditField::SdrBrightness => EditField::ColorMode,
+            EditField::SdrSaturation => EditField::SdrBrightness,
+            EditField::Primary => EditField::SdrSaturation,
             EditField::Enabled => EditField::Primary,
         }
     }
+
+    /// Whether the field is adjusted with left/right.
+    pub fn is_slider(self) -> bool {
+        matches!(
+            self,
+            EditField::Scale | EditField::SdrBrightness | EditField::SdrSaturation
+        )
+    }
+
+    /// Whether the field is chosen from a list of options.
+    pub fn has_picker(self) -> bool {
+        matches!(
+            self,
+            EditField::Resolution | EditField::RefreshRate | EditField::Mirror
+        )
+    }
 }
 
+/// Lowest and highest scale offered by the editor.
+pub const MIN_SCALE: f32 = 0.5;
+pub const MAX_SCALE: f32 = 3.0;
+/// Step for the SDR brightness and saturation sliders.
+pub const SDR_STEP: f32 = 0.05;
+
+/// How long status and error messages stay in the footer.
+pub const MESSAGE_TIMEOUT: Duration = Duration::from_secs(5);
+
 /// Application operating mode.
 #[derive(Clone, Debug, PartialEq)]
 pub enum AppMode {
@@ -64,8 +119,135 @@ pub enum AppMode {
         action: ConfirmAction,
         message: String,
     },
-    /// Profile selection
+    /// Profile manager screen
     ProfileSelect,
+    /// Text prompt (state in `App::prompt`)
+    Prompt,
+    /// Waiting for the user to keep a live-applied layout before it is reverted
+    KeepSettings { deadline: Instant },
+}
+
+/// Saved profile shown in the profile manager.
+#[derive(Clone, Debug)]
+pub struct ProfileEntry {
+    /// File stem in the profiles directory
+    pub file_name: String,
+    pub profile: Profile,
+}
+
+/// Single-line text input with a cursor (in characters).
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct TextInput {
+    pub value: String,
+    pub cursor: usize,
+}
+
+impl TextInput {
+    pub fn new(value: &str) -> Self {
+        Self {
+            value: value.to_string(),
+            cursor: value.chars().count(),
+        }
+    }
+
+    fn byte_index(&self, cursor: usize) -> usize {
+        self.value
+            .char_indices()
+            .nth(cursor)
+            .map_or(self.value.len(), |(i, _)| i)
+    }
+
+    pub fn insert(&mut self, c: char) {
+        let i = self.byte_index(self.cursor);
+        self.value.insert(i, c);
+        self.cursor += 1;
+    }
+
+    pub fn backspace(&mut self) {
+        if self.cursor > 0 {
+            self.cursor -= 1;
+            let i = self.byte_index(self.cursor);
+            self.value.remove(i);
+        }
+    }
+
+    pub fn delete(&mut self) {
+        if self.cursor < self.value.chars().count() {
+            let i = self.byte_index(self.cursor);
+            self.value.remove(i);
+        }
+    }
+
+    pub fn left(&mut self) {
+        self.cursor = self.cursor.saturating_sub(1);
+    }
+
+    pub fn right(&mut self) {
+        self.cursor = (self.cursor + 1).min(self.value.chars().count());
+    }
+
+    pub fn home(&mut self) {
+        self.cursor = 0;
+    }
+
+    pub fn end(&mut self) {
+        self.cursor = self.value.chars().count();
+    }
+}
+
+/// What a text prompt's input is used for.
+#[derive(Clone, Debug, PartialEq)]
+pub enum PromptAction {
+    /// Rename the profile with this file name
+    RenameProfile(String),
+    /// Save the edited layout as a named profile (fields: name, description)
+    SaveProfile,
+    /// Set the selected monitor's reserved area (fields: top, bottom, left, right)
+    SetReserved,
+}
+
+/// Text prompt with one or more labelled fields.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Prompt {
+    pub action: PromptAction,
+    pub title: String,
+    pub fields: Vec<(String, TextInput)>,
+    /// Index of the field receiving input
+    pub focused: usize,
+    /// Validation error from the last submit
+    pub error: Option<String>,
+}
+
+impl Prompt {
+    pub fn new(action: PromptAction, title: &str, fields: &[(&str, &str)]) -> Self {
+        Self {
+            action,
+            title: title.to_string(),
+            fields: fields
+                .iter()
+                .map(|(label, value)| (label.to_string(), TextInput::new(value)))
+                .collect(),
+            focused: 0,
+            error: None,
+        }
+    }
+
+    pub fn input_mut(&mut self) -> Option<&mut TextInput> {
+        self.fields.get_mut(self.focused).map(|(_, input)| input)
+    }
+
+    pub fn next_field(&mut self) {
+        if !self.fields.is_empty() {
+            self.focused = (self.focused + 1) % self.fields.len();
+        }
+    }
+
+    /// Trimmed value of the field at `index`.
+    pub fn value(&self, index: usize) -> &str {
+        self.fields
+            .get(index)
+            .map_or("", |(_, input)| input.value.trim())
+    }
 }
 
 /// Snapshot of app state for undo.
@@ -91,26 +273,37 @@ pub struct App {
     pub config_path: PathBuf,
     /// Change history for undo
     pub history: Vec<AppStateSnapshot>,
-    /// Available profile names
-    pub profiles: Vec<String>,
+    /// Saved profiles, sorted by file name
+    pub profiles: Vec<ProfileEntry>,
+    /// Highlighted profile in the profile manager
+    pub selected_profile: usize,
+    /// Open text prompt (Prompt mode)
+    pub prompt: Option<Prompt>,
+    /// Profile last loaded or saved, offered as the default save name
+    pub current_profile: Option<String>,
     /// Theme
     pub theme: Theme,
+    /// General settings from config.toml
+    pub settings: GeneralSettings,
+    /// Highlighted entry of the open option picker (Editing mode)
+    pub picker: Option<usize>,
+    /// Position and unsaved flag when Moving mode started (for cancel)
+    pub move_origin: Option<(Position, bool)>,
+    /// Live layout before the last apply, re-applied if it is not kept in time
+    pub revert_to: Option<Vec<Monitor>>,
+    /// `A` was pressed; the next key picks an arrangement
+    pub arrange_pending: bool,
     /// Status message
     pub status_message: Option<String>,
     /// Error message
     pub error_message: Option<String>,
+    /// When the current status/error message was set
+    pub message_since: Option<Instant>,
 }
 
 impl App {
     pub fn new(monitors: Vec<Monitor>, config_path: PathBuf, theme: Theme) -> Self {
-        let profiles = crate::config::list_profiles(
-            config_path
-                .parent()
-                .unwrap_or_else(|| std::path::Path::new("."))
-                .join("profiles")
-                .as_path(),
-        );
-        Self {
+        let mut app = Self {
             monitors,
             selected_monitor: 0,
             mode: AppMode::Normal,
@@ -118,11 +311,22 @@ impl App {
             unsaved_changes: false,
             config_path,
             history: Vec::new(),
-            profiles,
+            profiles: Vec::new(),
+            selected_profile: 0,
+            prompt: None,
+            current_profile: None,
             theme,
+            settings: GeneralSettings::default(),
+            picker: None,
+            move_origin: None,
+            revert_to: None,
+            arrange_pending: false,
             status_message: None,
             error_message: None,
-        }
+            message_since: None,
+        };
+        app.reload_profiles();
+        app
     }
 
     pub fn selected(&self) -> Option<&Monitor> {
@@ -154,16 +358,920 @@ impl App {
         }
     }
 
+    /// Merge a fresh monitor list from Hyprland (after hotplug).
+    /// Connected monitors keep their pending edits; new ones are added, removed ones dropped.
+    /// Undo history is dropped when the set of outputs changes, as it would restore stale ones.
+    pub fn merge_monitors(&mut self, fresh: Vec<Monitor>) {
+        let selected_name = self.selected().map(|m| m.name.clone());
+        let mut old_names: Vec<&str> = self.monitors.iter().map(|m| m.name.as_str()).collect();
+        let mut new_names: Vec<&str> = fresh.iter().map(|m| m.name.as_str()).collect();
+        old_names.sort_unstable();
+        new_names.sort_unstable();
+        if old_names != new_names {
+            self.history.clear();
+        }
+        let merged: Vec<Monitor> = fresh
+            .into_iter()
+            .map(
+                |live| match self.monitors.iter().find(|m| m.name == live.name) {
+                    Some(edited) if self.unsaved_changes => Monitor {
+                        description: live.description,
+                        available_resolutions: live.available_resolutions,
+                        available_refresh_rates: live.available_refresh_rates,
+                        available_modes: live.available_modes,
+                        ..edited.clone()
+                    },
+                    _ => live,
+                },
+            )
+            .collect();
+        self.monitors = merged;
+        self.selected_monitor = selected_name
+            .and_then(|n| self.monitors.iter().position(|m| m.name == n))
+            .unwrap_or(0)
+            .min(self.monitors.len().saturating_sub(1));
+    }
+
+    /// Options offered by the picker for a field of the selected monitor.
+    pub fn picker_options(&self, field: EditField) -> Vec<String> {
+        let Some(m) = self.selected() else {
+            return Vec::new();
+        };
+        match field {
+            EditField::Resolution => m
+                .available_resolutions
+                .iter()
+                .map(|r| r.to_string())
+                .collect(),
+            EditField::RefreshRate => self
+                .refresh_rates()
+                .iter()
+                .map(|hz| format!("{:.2} Hz", hz))
+                .collect(),
+            EditField::Mirror => std::iter::once("None".to_string())
+                .chain(self.mirror_sources())
+                .collect(),
+            _ => Vec::new(),
+        }
+    }
+
+    /// Outputs the selected monitor could mirror (every other monitor).
+    fn mirror_sources(&self) -> Vec<String> {
+        let Some(m) = self.selected() else {
+            return Vec::new();
+        };
+        self.monitors
+            .iter()
+            .filter(|o| o.name != m.name)
+            .map(|o| o.name.clone())
+            .collect()
+    }
+
+    /// Make the selected monitor mirror `source` (None stops mirroring); rejects cycles.
+    pub fn set_mirror(&mut self, source: Option<String>) -> Result<(), String> {
+        let Some(name) = self.selected().map(|m| m.name.clone()) else {
+            return Ok(());
+        };
+        if let Some(src) = &source {
+            if mirror_cycle(&self.monitors, &name, src) {
+                return Err(format!(
+                    "{} cannot mirror {}: that would be a mirror cycle",
+                    name, src
+                ));
+            }
+        }
+        if self.selected().is_some_and(|m| m.mirror_of == source) {
+            return Ok(());
+        }
+        self.push_history();
+        if let Some(m) = self.selected_mut() {
+            m.mirror_of = source;
+        }
+        self.unsaved_changes = true;
+        Ok(())
+    }
+
+    /// Refresh rates valid for the selected monitor's current resolution.
+    fn refresh_rates(&self) -> Vec<f32> {
+        let Some(m) = self.selected() else {
+            return Vec::new();
+        };
+        let rates = m.refresh_rates_for(&m.resolution);
+        if rates.is_empty() {
+            m.available_refresh_rates.clone()
+        } else {
+            rates
+        }
+    }
+
+    /// Open the option picker for a field, highlighting the current value.
+    pub fn open_picker(&mut self, field: EditField) {
+        let Some(m) = self.selected() else {
+            return;
+        };
+        let current = match field {
+            EditField::Resolution => m
+                .available_resolutions
+                .iter()
+                .position(|r| *r == m.resolution),
+            EditField::RefreshRate => self
+                .refresh_rates()
+                .iter()
+                .position(|hz| (hz - m.refresh_rate).abs() < 0.01),
+            EditField::Mirror => Some(
+                m.mirror_of
+                    .as_ref()
+                    .and_then(|s| self.mirror_sources().iter().position(|o| o == s))
+                    .map_or(0, |i| i + 1),
+            ),
+            _ => return,
+        };
+        self.picker = Some(current.unwrap_or(0));
+    }
+
+    /// Move the picker highlight by `delta`, clamped to the option list.
+    pub fn picker_move(&mut self, field: EditField, delta: isize) {
+        let len = self.picker_options(field).len();
+        if let Some(i) = self.picker {
+            self.picker = Some(i.saturating_add_signed(delta).min(len.saturating_sub(1)));
+        }
+    }
+
+    /// Apply the highlighted picker option and close the picker.
+    pub fn picker_select(&mut self, field: EditField) {
+        let Some(i) = self.picker.take() else {
+            return;
+        };
+        match field {
+            EditField::Resolution => {
+                let Some(res) = self
+                    .selected()
+                    .and_then(|m| m.available_resolutions.get(i).cloned())
+                else {
+                    return;
+                };
+                self.set_resolution(res);
+            }
+            EditField::RefreshRate => {
+                let Some(hz) = self.refresh_rates().get(i).copied() else {
+                    return;
+                };
+                self.push_history();
+                if let Some(m) = self.selected_mut() {
+                    m.refresh_rate = hz;
+                    m.mode_spec = None;
+                }
+                self.unsaved_changes = true;
+            }
+            EditField::Mirror => {
+                let source = i
+                    .checked_sub(1)
+                    .and_then(|i| self.mirror_sources().get(i).cloned());
+                if let Err(e) = self.set_mirror(source) {
+                    self.set_error(e);
+                }
+            }
+            _ => {}
+        }
+    }
+
+    /// Set the selected monitor's resolution, keeping the refresh rate if the new mode supports it.
+    pub fn set_resolution(&mut self, res: Resolution) {
+        self.push_history();
+        if let Some(m) = self.selected_mut() {
+            let rates = m.refresh_rates_for(&res);
+            if !rates.is_empty() && !rates.iter().any(|hz| (hz - m.refresh_rate).abs() < 0.01) {
+                m.refresh_rate = rates[0];
+            }
+            m.available_refresh_rates = rates;
+            m.resolution = res;
+            m.mode_spec = None;
+        }
+        self.unsaved_changes = true;
+    }
+
+    /// Step the selected monitor's scale by `steps` times `scale_step`, landing on the
+    /// nearest scale Hyprland accepts for its resolution.
+    pub fn step_scale(&mut self, steps: f32) {
+        let step = self.settings.scale_step;
+        let Some((scale, resolution)) = self.selected().map(|m| (m.scale, m.resolution.clone()))
+        else {
+            return;
+        };
+        // Only scales Hyprland accepts: the valid one closest to the target, moving at least one.
+        let target = scale + steps * step;
+        let Some(new) = hyprland::valid_scales(&resolution, MIN_SCALE..=MAX_SCALE)
+            .into_iter()
+            .filter(|s| {
+                if steps > 0.0 {
+                    *s > scale + 1e-4
+                } else {
+                    *s < scale - 1e-4
+                }
+            })
+            .min_by(|a, b| (a - target).abs().total_cmp(&(b - target).abs()))
+        else {
+            return;
+        };
+        self.push_history();
+        if let Some(m) = self.selected_mut() {
+            m.scale = new;
+        }
+        self.unsaved_changes = true;
+    }
+
+    /// Step a slider field: scale by `scale_step`, SDR brightness/saturation by 0.05.
+    pub fn step_field(&mut self, field: EditField, steps: f32) {
+        let (range, current) = match (field, self.selected()) {
+            (EditField::Scale, _) => return self.step_scale(steps),
+            (EditField::SdrBrightness, Some(m)) => (SDR_BRIGHTNESS_RANGE, m.sdr_brightness),
+            (EditField::SdrSaturation, Some(m)) => (SDR_SATURATION_RANGE, m.sdr_saturation),
+            _ => return,
+        };
+        let old = current.unwrap_or(1.0);
+        let new = (((old + steps * SDR_STEP) * 100.0).round() / 100.0)
+            .clamp(*range.start(), *range.end());
+        if current.is_some() && (new - old).abs() < f32::EPSILON {
+            return;
+        }
+        self.push_history();
+        if let Some(m) = self.selected_mut() {
+            match field {
+                EditField::SdrBrightness => m.sdr_brightness = Some(new),
+                _ => m.sdr_saturation = Some(new),
+            }
+        }
+        self.unsaved_changes = true;
+    }
+
+    /// Toggle a boolean-like field (Transform cycles, Primary is exclusive).
+    pub fn toggle_field(&mut self, field: EditField) {
+        let idx = self.selected_monitor;
+        if idx >= self.monitors.len() {
+            return;
+        }
+        match field {
+            EditField::Transform => {
+                self.push_history();
+                let m = &mut self.monitors[idx];
+                m.transform = m.transform.next();
+            }
+            EditField::Vrr => {
+                self.push_history();
+                let m = &mut self.monitors[idx];
+                m.vrr = Vrr::cycle(m.vrr);
+            }
+            EditField::Bitdepth => {
+                self.push_history();
+                let m = &mut self.monitors[idx];
+                m.bitdepth = if m.bitdepth == 10 { 8 } else { 10 };
+            }
+            EditField::ColorMode => {
+                self.push_history();
+                let m = &mut self.monitors[idx];
+                m.color_mode = ColorMode::cycle(m.color_mode);
+            }
+            // Enter on a colour slider goes back to Hyprland's default.
+            EditField::SdrBrightness | EditField::SdrSaturation => {
+                let m = &self.monitors[idx];
+                let value = match field {
+                    EditField::SdrBrightness => m.sdr_brightness,
+                    _ => m.sdr_saturation,
+                };
+                if value.is_none() {
+                    return;
+                }
+                self.push_history();
+                let m = &mut self.monitors[idx];
+                match field {
+                    EditField::SdrBrightness => m.sdr_brightness = None,
+                    _ => m.sdr_saturation = None,
+                }
+            }
+            EditField::Primary => {
+                // Makes the monitor the only primary; there is always one.
+                if self.monitors[idx].primary {
+                    return;
+                }
+                self.push_history();
+                for mon in self.monitors.iter_mut() {
+                    mon.primary = false;
+                }
+                self.monitors[idx].primary = true;
+            }
+            EditField::Enabled => {
+                self.push_history();
+                let m = &mut self.monitors[idx];
+                m.enabled = !m.enabled;
+            }
+            _ => return,
+        }
+        self.unsaved_changes = true;
+    }
+
+    /// Enter Moving mode for the selected monitor, taking an undo snapshot.
+    pub fn begin_move(&mut self) {
+        let Some(pos) = self.selected().map(|m| m.position.clone()) else {
+            return;
+        };
+        self.push_history();
+        self.move_origin = Some((pos, self.unsaved_changes));
+        self.mode = AppMode::Moving;
+    }
+
+    /// Nudge the selected monitor and snap it to a neighbour edge closer than the step.
+    pub fn nudge_selected(&mut self, dx: i32, dy: i32) {
+        let idx = self.selected_monitor;
+        let Some(m) = self.monitors.get_mut(idx) else {
+            return;
+        };
+        m.position.x += dx;
+        m.position.y += dy;
+        m.position_spec = None;
+        let threshold = dx.abs().max(dy.abs());
+        let snapped = snap_to_neighbours(&self.monitors, idx, threshold);
+        self.monitors[idx].position = snapped;
+        self.unsaved_changes = true;
+    }
+
+    /// Place the selected monitor at an absolute layout position (mouse drag).
+    pub fn place_selected(&mut self, x: i32, y: i32) {
+        if let Some(m) = self.selected_mut() {
+            m.position = Position { x, y };
+            m.position_spec = None;
+            self.unsaved_changes = true;
+        }
+    }
+
+    /// Snap the selected monitor to neighbour edges closer than `threshold` pixels.
+    pub fn snap_selected(&mut self, threshold: i32) {
+        let idx = self.selected_monitor;
+        if idx < self.monitors.len() {
+            self.monitors[idx].position = snap_to_neighbours(&self.monitors, idx, threshold);
+        }
+    }
+
+    /// Wait for the key choosing an arrangement.
+    pub fn begin_arrange(&mut self) {
+        self.arrange_pending = true;
+        self.set_status("Arrange: r row, c centred row, v column, l L-shape".to_string());
+    }
+
+    /// Rearrange all enabled monitors as one undo step.
+    pub fn arrange(&mut self, arrangement: Arrangement) {
+        self.arrange_pending = false;
+        let mut arranged = self.monitors.clone();
+        if let Err(e) = hyprland::arrange(&mut arranged, arrangement) {
+            self.set_error(e);
+            return;
+        }
+        if arranged == self.monitors {
+            self.set_status(format!("Already arranged as {}", arrangement));
+            return;
+        }
+        self.push_history();
+        self.monitors = arranged;
+        self.unsaved_changes = true;
+        self.set_status(format!("Arranged as {}", arrangement));
+    }
+
+    /// Leave Moving mode keeping the new position (drops the snapshot if nothing moved).
+    pub fn commit_move(&mut self) {
+        if let Some((origin, unsaved)) = self.move_origin.take() {
+            if self.selected().is_some_and(|m| m.position == origin) {
+                // Restore the snapshot so an automatic position survives a no-op move.
+                if let Some(snap) = self.history.pop() {
+                    self.monitors = snap.monitors;
+                }
+                self.unsaved_changes = unsaved;
+            }
+        }
+        self.mode = AppMode::Normal;
+    }
+
+    /// Leave Moving mode restoring the snapshot taken by `begin_move`.
+    pub fn cancel_move(&mut self) {
+        if let Some((_, unsaved)) = self.move_origin.take() {
+            if let Some(snap) = self.history.pop() {
+                self.monitors = snap.monitors;
+            }
+            self.unsaved_changes = unsaved;
+        }
+        self.mode = AppMode::Normal;
+    }
+
+    /// Apply the edited layout live and start the keep-or-revert countdown.
+    pub fn apply_live(&mut self, backend: &dyn MonitorBackend) {
+        if let Some(issue) = hyprland::validate_layout(&self.monitors)
+            .into_iter()
+            .find(|i| i.severity == Severity::Error)
+        {
+            self.set_error(format!("Not applied: {}", issue));
+            return;
+        }
+        let mut previous = match backend.get_monitors() {
+            Ok(m) => m,
+            Err(e) => {
+                self.set_error(format!("Not applied, cannot read current layout: {}", e));
+                return;
+            }
+        };
+        // Hyprland does not report `addreserved` insets; reverting clears the ones applied here.
+        for m in previous.iter_mut() {
+            if self
+                .monitors
+                .iter()
+                .any(|a| a.name == m.name && a.reserved.is_some())
+            {
+                m.reserved = Some(Reserved::default());
+            }
+        }
+        self.push_history();
+        match backend.apply_all(&self.monitors) {
+            Ok(()) => {}
+            // Part of the layout is already live; offer the revert anyway.
+            Err(e @ ApplyError::Partial { .. }) => self.set_error(e.to_string()),
+            Err(ApplyError::Ipc(e)) => {
+                self.history.pop();
+                self.set_error(format!("Not applied: {:#}", e));
+                return;
+            }
+        }
+        self.start_revert_countdown(previous);
+    }
+
+    /// Re-apply the layout that was live before the last apply.
+    pub fn revert_live(&mut self, backend: &dyn MonitorBackend) {
+        let Some(previous) = self.revert_applied() else {
+            return;
+        };
+        match backend.apply_all(&previous) {
+            Ok(()) => {
+                self.set_status("Reverted to previous layout (u restores the edits).".to_string())
+            }
+            Err(e) => self.set_error(format!("Revert failed: {}", e)),
+        }
+    }
+
+    /// Write the edited layout into Hyprland's config files (see `persist_mode`).
+    pub fn persist_config(&mut self) {
+        let options = ExportOptions {
+            by_description: self.settings.export_by_description,
+            syntax: self.settings.export_syntax,
+        };
+        let block = hyprland::generate_config_with(&self.monitors, &options);
+        let target = self.settings.persist_target();
+        match hyprland::persist(&target, &block) {
+            Ok(report) if report.written.is_empty() => {
+                self.set_status("Hyprland config already up to date.".to_string())
+            }
+            Ok(report) => {
+                let files: Vec<String> = report
+                    .written
+                    .iter()
+                    .map(|p| p.display().to_string())
+                    .collect();
+                let backups = if report.backups.is_empty() {
+                    String::new()
+                } else {
+                    format!(" ({} backup(s) kept)", report.backups.len())
+                };
+                self.set_status(format!("Wrote {}{}", files.join(", "), backups));
+            }
+            Err(e) => self.set_error(format!("Failed to write Hyprland config: {:#}", e)),
+        }
+    }
+
+    /// Re-read monitors after a hotplug event, keeping pending edits.
+    pub fn refresh_monitors(&mut self, backend: &dyn MonitorBackend) {
+        match backend.get_monitors() {
+            Ok(monitors) => {
+                self.merge_monitors(monitors);
+                self.set_status("Monitor layout changed.".to_string());
+            }
+            Err(e) => self.set_error(format!("Failed to refresh monitors: {}", e)),
+        }
+    }
+
+    /// Discard edits and load the live layout (undoable).
+    pub fn reset_to_live(&mut self, backend: &dyn MonitorBackend) {
+        match backend.get_monitors() {
+            Ok(monitors) => {
+                self.push_history();
+                self.monitors = monitors;
+                self.selected_monitor = self
+                    .selected_monitor
+                    .min(self.monitors.len().saturating_sub(1));
+                self.unsaved_changes = false;
+            }
+            Err(e) => self.set_error(format!("Failed to read monitors: {}", e)),
+        }
+    }
+
+    /// Start the "keep these settings?" countdown after a live apply.
+    pub fn start_revert_countdown(&mut self, previous: Vec<Monitor>) {
+        let timeout = Duration::from_secs(self.settings.preview_timeout);
+        self.revert_to = Some(previous);
+        self.mode = AppMode::KeepSettings {
+            deadline: Instant::now() + timeout,
+        };
+    }
+
+    /// Whole seconds left before an unconfirmed apply is reverted.
+    pub fn revert_seconds_left(&self) -> Option<u64> {
+        match self.mode {
+            AppMode::KeepSettings { deadline } => {
+                let left = deadline.saturating_duration_since(Instant::now());
+                Some(left.as_secs() + u64::from(left.subsec_nanos() > 0))
+            }
+            _ => None,
+        }
+    }
+
+    /// Whether the countdown has expired at `now`.
+    pub fn revert_due(&self, now: Instant) -> bool {
+        matches!(self.mode, AppMode::KeepSettings { deadline } if now >= deadline)
+    }
+
+    /// Keep the applied layout and drop the revert snapshot.
+    /// The layout is live but not yet saved, so `unsaved_changes` stays as it was.
+    pub fn keep_applied(&mut self) {
+        self.revert_to = None;
+        self.mode = AppMode::Normal;
+    }
+
+    /// Restore the pre-apply layout in the editor; the rejected layout stays undoable.
+    /// Returns the layout that must be re-applied to Hyprland.
+    pub fn revert_applied(&mut self) -> Option<Vec<Monitor>> {
+        self.mode = AppMode::Normal;
+        let previous = self.revert_to.take()?;
+        self.push_history();
+        let selected_name = self.selected().map(|m| m.name.clone());
+        self.monitors = previous.clone();
+        self.selected_monitor = selected_name
+            .and_then(|n| self.monitors.iter().position(|m| m.name == n))
+            .unwrap_or(0);
+        self.unsaved_changes = false;
+        Some(previous)
+    }
+
     pub fn set_status(&mut self, msg: String) {
         self.status_message = Some(msg);
+        self.message_since = Some(Instant::now());
     }
 
     pub fn set_error(&mut self, msg: String) {
         self.error_message = Some(msg);
+        self.message_since = Some(Instant::now());
     }
 
     pub fn clear_messages(&mut self) {
         self.status_message = None;
         self.error_message = None;
+        self.message_since = None;
+    }
+
+    /// Clear messages once they have been shown for `MESSAGE_TIMEOUT`.
+    pub fn expire_messages(&mut self, now: Instant) {
+        if self
+            .message_since
+            .is_some_and(|t| now.duration_since(t) >= MESSAGE_TIMEOUT)
+        {
+            self.clear_messages();
+        }
+    }
+
+    /// Directory holding saved profiles, next to config.toml.
+    pub fn profiles_dir(&self) -> PathBuf {
+        self.config_path
+            .parent()
+            .unwrap_or_else(|| Path::new("."))
+            .join("profiles")
+    }
+
+    fn profile_path(&self, file_name: &str) -> PathBuf {
+        self.profiles_dir().join(format!("{}.toml", file_name))
+    }
+
+    /// Re-read the profiles directory, keeping the highlighted profile if it still exists.
+    pub fn reload_profiles(&mut self) {
+        let current = self.selected_profile_entry().map(|e| e.file_name.clone());
+        self.profiles = config::load_profiles(&self.profiles_dir())
+            .into_iter()
+            .map(|(file_name, profile)| ProfileEntry { file_name, profile })
+            .collect();
+        self.selected_profile = current
+            .and_then(|n| self.profiles.iter().position(|e| e.file_name == n))
+            .unwrap_or(self.selected_profile)
+            .min(self.profiles.len().saturating_sub(1));
+    }
+
+    pub fn selected_profile_entry(&self) -> Option<&ProfileEntry> {
+        self.profiles.get(self.selected_profile)
+    }
+
+    /// Move the profile highlight by `delta`, clamped to the list.
+    pub fn profile_move(&mut self, delta: isize) {
+        self.selected_profile = self
+            .selected_profile
+            .saturating_add_signed(delta)
+            .min(self.profiles.len().saturating_sub(1));
+    }
+
+    /// Open the profile manager with a freshly read profile list.
+    pub fn open_profiles(&mut self) {
+        self.reload_profiles();
+        self.mode = AppMode::ProfileSelect;
+    }
+
+    /// Layout the highlighted profile would produce on the connected monitors.
+    pub fn profile_preview(&self) -> Vec<Monitor> {
+        self.selected_profile_entry()
+            .map(|e| e.profile.to_monitors(&self.monitors))
+            .unwrap_or_default()
+    }
+
+    /// Load the highlighted profile into the editor (undoable).
+    /// Connected monitors the profile does not mention keep their current settings.
+    pub fn load_selected_profile(&mut self) -> bool {
+        let Some(entry) = self.selected_profile_entry() else {
+            return false;
+        };
+        let name = entry.profile.name.clone();
+        let file_name = entry.file_name.clone();
+        let matched = entry.profile.match_monitors(&self.monitors);
+        if matched.monitors.is_empty() {
+            self.set_error(format!("Profile '{}' matches no connected monitor", name));
+            return false;
+        }
+        self.push_history();
+        for m in matched.monitors {
+            if let Some(slot) = self.monitors.iter_mut().find(|cur| cur.name == m.name) {
+                *slot = m;
+            }
+        }
+        self.unsaved_changes = true;
+        self.current_profile = Some(file_name);
+        self.mode = AppMode::Normal;
+        if matched.unmatched.is_empty() {
+            self.set_status(format!("Loaded profile '{}'", name));
+        } else {
+            self.set_status(format!(
+                "Loaded profile '{}' (not connected: {})",
+                name,
+                matched.unmatched.join(", ")
+            ));
+        }
+        true
+    }
+
+    /// Load the highlighted profile and apply it live with the keep-or-revert countdown.
+    pub fn apply_selected_profile(&mut self, backend: &dyn MonitorBackend) {
+        if self.load_selected_profile() {
+            self.apply_live(backend);
+        }
+    }
+
+    /// Open the rename prompt for the highlighted profile.
+    pub fn begin_rename_profile(&mut self) {
+        let Some(file_name) = self.selected_profile_entry().map(|e| e.file_name.clone()) else {
+            return;
+        };
+        self.prompt = Some(Prompt::new(
+            PromptAction::RenameProfile(file_name.clone()),
+            "Rename Profile",
+            &[("Name", &file_name)],
+        ));
+        self.mode = AppMode::Prompt;
+    }
+
+    /// Rename a saved profile file and the name stored inside it.
+    pub fn rename_profile(&mut self, from: &str, to: &str) -> Result<(), String> {
+        config::validate_profile_name(to)?;
+        let to = to.trim();
+        if to == from {
+            return Ok(());
+        }
+        let target = self.profile_path(to);
+        if target.exists() {
+            return Err(format!("Profile '{}' already exists", to));
+        }
+        let source = self.profile_path(from);
+        let mut profile = config::load_profile(&source).map_err(|e| e.to_string())?;
+        profile.name = to.to_string();
+        profile.modified_at = Some(chrono::Utc::now().to_rfc3339());
+        config::save_profile(&target, &profile).map_err(|e| e.to_string())?;
+        std::fs::remove_file(&source).map_err(|e| e.to_string())?;
+        if self.current_profile.as_deref() == Some(from) {
+            self.current_profile = Some(to.to_string());
+        }
+        self.reload_profiles();
+        if let Some(i) = self.profiles.iter().position(|e| e.file_name == to) {
+            self.selected_profile = i;
+        }
+        self.set_status(format!("Renamed profile '{}' to '{}'", from, to));
+        Ok(())
+    }
+
+    /// Save a copy of the highlighted profile as "<name>-copy" (or "-copy-N").
+    pub fn duplicate_selected_profile(&mut self) {
+        let Some(entry) = self.selected_profile_entry().cloned() else {
+            return;
+        };
+        let base = format!("{}-copy", entry.file_name);
+        let name = (1..)
+            .map(|n| {
+                if n == 1 {
+                    base.clone()
+                } else {
+                    format!("{}-{}", base, n)
+                }
+            })
+            .find(|n| !self.profile_path(n).exists())
+            .unwrap_or(base);
+        let now = chrono::Utc::now().to_rfc3339();
+        let copy = Profile {
+            name: name.clone(),
+            created_at: Some(now.clone()),
+            modified_at: Some(now),
+            ..entry.profile
+        };
+        match config::save_profile(&self.profile_path(&name), &copy) {
+            Ok(()) => {
+                self.reload_profiles();
+                if let Some(i) = self.profiles.iter().position(|e| e.file_name == name) {
+                    self.selected_profile = i;
+                }
+                self.set_status(format!("Duplicated profile as '{}'", name));
+            }
+            Err(e) => self.set_error(format!("Failed to duplicate profile: {}", e)),
+        }
+    }
+
+    /// Ask before deleting the highlighted profile.
+    pub fn begin_delete_profile(&mut self) {
+        let Some(file_name) = self.selected_profile_entry().map(|e| e.file_name.clone()) else {
+            return;
+        };
+        self.mode = AppMode::Confirm {
+            message: format!("Delete profile '{}'?", file_name),
+            action: ConfirmAction::DeleteProfile(file_name),
+        };
+    }
+
+    /// Open the save prompt, pre-filled with the current profile's name and description.
+    pub fn begin_save_profile(&mut self) {
+        let name = self.current_profile.clone().unwrap_or_default();
+        let description = self
+            .profiles
+            .iter()
+            .find(|e| e.file_name == name)
+            .and_then(|e| e.profile.description.clone())
+            .unwrap_or_default();
+        self.prompt = Some(Prompt::new(
+            PromptAction::SaveProfile,
+            "Save Profile",
+            &[("Name", &name), ("Description", &description)],
+        ));
+        self.mode = AppMode::Prompt;
+    }
+
+    /// Save the edited layout as profile `name`. Overwriting keeps the original `created_at`.
+    pub fn save_profile_as(
+        &mut self,
+        name: &str,
+        description: Option<String>,
+    ) -> Result<(), String> {
+        config::validate_profile_name(name)?;
+        let name = name.trim();
+        let dir = self.profiles_dir();
+        std::fs::create_dir_all(&dir).map_err(|e| e.to_string())?;
+        let path = self.profile_path(name);
+        let mut profile = Profile::from_monitors(name.to_string(), description, &self.monitors);
+        if let Ok(existing) = config::load_profile(&path) {
+            profile.created_at = existing.created_at.or(profile.created_at);
+        }
+        config::save_profile(&path, &profile).map_err(|e| e.to_string())?;
+        self.current_profile = Some(name.to_string());
+        self.unsaved_changes = false;
+        self.reload_profiles();
+        self.set_status(format!("Saved profile '{}'", name));
+        Ok(())
+    }
+
+    /// Delete a saved profile file.
+    pub fn delete_profile(&mut self, file_name: &str) {
+        match std::fs::remove_file(self.profile_path(file_name)) {
+            Ok(()) => self.set_status(format!("Deleted profile '{}'", file_name)),
+            Err(e) => self.set_error(format!("Failed to delete profile: {}", e)),
+        }
+        self.reload_profiles();
+    }
+
+    /// Submit the open prompt; on a validation error the prompt stays open.
+    pub fn submit_prompt(&mut self) {
+        let Some(prompt) = self.prompt.clone() else {
+            return;
+        };
+        let result = match &prompt.action {
+            PromptAction::RenameProfile(from) => self.rename_profile(from, prompt.value(0)),
+            PromptAction::SetReserved => self.set_reserved_from_prompt(&prompt),
+            PromptAction::SaveProfile => {
+                let name = prompt.value(0).to_string();
+                let description = Some(prompt.value(1).to_string()).filter(|d| !d.is_empty());
+                match config::validate_profile_name(&name) {
+                    Err(e) => Err(e),
+                    Ok(()) if self.profile_path(&name).exists() => {
+                        self.prompt = None;
+                        self.mode = AppMode::Confirm {
+                            message: format!("Profile '{}' exists. Overwrite?", name),
+                            action: ConfirmAction::OverwriteProfile { name, description },
+                        };
+                        return;
+                    }
+                    Ok(()) => self.save_profile_as(&name, description),
+                }
+            }
+        };
+        match result {
+            Ok(()) => self.close_prompt(),
+            Err(e) => {
+                if let Some(p) = self.prompt.as_mut() {
+                    p.error = Some(e);
+                }
+            }
+        }
+    }
+
+    /// Open the reserved-area prompt for the selected monitor.
+    pub fn begin_edit_reserved(&mut self) {
+        let Some(r) = self.selected().map(|m| m.reserved.unwrap_or_default()) else {
+            return;
+        };
+        let values = [r.top, r.bottom, r.left, r.right].map(|v| v.to_string());
+        self.prompt = Some(Prompt::new(
+            PromptAction::SetReserved,
+            "Reserved Area (px)",
+            &[
+                ("Top", &values[0]),
+                ("Bottom", &values[1]),
+                ("Left", &values[2]),
+                ("Right", &values[3]),
+            ],
+        ));
+        self.mode = AppMode::Prompt;
+    }
+
+    /// Set the selected monitor's reserved area; all zeros clears it.
+    /// A cleared inset stays as an empty one so a live apply resets it in Hyprland.
+    pub fn set_reserved(&mut self, reserved: Option<Reserved>) {
+        let Some(current) = self.selected().map(|m| m.reserved) else {
+            return;
+        };
+        let reserved = match reserved.filter(|r| !r.is_empty()) {
+            None if current.is_some() => Some(Reserved::default()),
+            r => r,
+        };
+        if current == reserved {
+            return;
+        }
+        self.push_history();
+        if let Some(m) = self.selected_mut() {
+            m.reserved = reserved;
+        }
+        self.unsaved_changes = true;
+    }
+
+    fn set_reserved_from_prompt(&mut self, prompt: &Prompt) -> Result<(), String> {
+        let mut values = [0u32; 4];
+        for (i, v) in values.iter_mut().enumerate() {
+            let text = prompt.value(i);
+            if !text.is_empty() {
+                *v = text.parse().map_err(|_| {
+                    format!("{} must be a whole number of pixels", prompt.fields[i].0)
+                })?;
+            }
+        }
+        let [top, bottom, left, right] = values;
+        self.set_reserved(Some(Reserved {
+            top,
+            bottom,
+            left,
+            right,
+        }));
+        Ok(())
+    }
+
+    /// Close the prompt and return to the screen that opened it.
+    pub fn close_prompt(&mut self) {
+        if let Some(prompt) = self.prompt.take() {
+            self.mode = match prompt.action {
+                PromptAction::RenameProfile(_) => AppMode::ProfileSelect,
+                PromptAction::SaveProfile => AppMode::Normal,
+                PromptAction::SetReserved => AppMode::Editing {
+                    field: EditField::Reserved,
+                },
+            };
+        }
     }
 }
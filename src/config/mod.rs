@@ -3,5 +3,10 @@
 mod profiles;
 mod settings;
 
-pub use profiles::{load_profile, list_profiles, save_profile, MonitorConfig, Profile};
-pub use settings::{AppConfig, GeneralSettings, KeybindingsConfig, Theme, ThemeConfig};
+pub use profiles::{
+    list_profiles, load_profile, load_profiles, save_profile, validate_profile_name, MonitorConfig,
+    Profile, ProfileMatch,
+};
+pub use settings::{
+    load_config, AppConfig, GeneralSettings, KeybindingsConfig, Theme, ThemeConfig,
+};
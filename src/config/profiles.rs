@@ -1,6 +1,9 @@
 //! Monitor profile management.
 
-use crate::hyprland::{Monitor, Position, Resolution, Transform};
+use crate::hyprland::{
+    resolve_auto_positions, ColorMode, ModeSpec, Monitor, MonitorRule, OutputSelector, Position,
+    PositionSpec, Reserved, Resolution, Transform, Vrr, SDR_BRIGHTNESS_RANGE, SDR_SATURATION_RANGE,
+};
 use chrono::Utc;
 use serde::{Deserialize, Serialize};
 use std::path::Path;
@@ -8,24 +11,49 @@ use std::path::Path;
 /// Monitor config for profile (serializable).
 #[derive(Clone, Debug, Serialize, Deserialize)]
 pub struct MonitorConfig {
+    /// Connector name when saved (e.g. "DP-1"); fallback when the description doesn't match
     pub name: String,
+    /// Monitor description (make, model, serial) used to find it on any connector
+    #[serde(default)]
+    pub description: Option<String>,
+    /// "WxH", or `preferred`, `highres`, `highrr`, `maxwidth` to let Hyprland pick
     #[serde(default)]
     pub resolution: Option<String>,
     #[serde(default)]
     pub refresh_rate: Option<f32>,
-    pub position: [i32; 2],
-    #[serde(default = "one_f32")]
-    pub scale: f32,
+    /// `[x, y]`, or `auto`, `auto-right`, `auto-left`, `auto-up`, `auto-down`
+    pub position: PositionSpec,
+    /// Unset for Hyprland's `auto` scale: the monitor keeps the scale it has
+    #[serde(default)]
+    pub scale: Option<f32>,
     #[serde(default)]
     pub transform: String,
     #[serde(default = "default_true")]
     pub enabled: bool,
     #[serde(default)]
     pub primary: bool,
+    /// Connector name of the output this one mirrors
+    #[serde(default)]
+    pub mirror_of: Option<String>,
+    /// `off`, `on` or `fullscreen_only`; unset leaves it to Hyprland
+    #[serde(default)]
+    pub vrr: Option<Vrr>,
+    #[serde(default = "eight_u8")]
+    pub bitdepth: u8,
+    /// `srgb`, `wide`, `hdr` or `hdredid`
+    #[serde(default)]
+    pub color_mode: Option<ColorMode>,
+    #[serde(default)]
+    pub sdr_brightness: Option<f32>,
+    #[serde(default)]
+    pub sdr_saturation: Option<f32>,
+    /// `{ top, bottom, left, right }` kept free of windows
+    #[serde(default)]
+    pub reserved: Option<Reserved>,
 }
 
-fn one_f32() -> f32 {
-    1.0
+fn eight_u8() -> u8 {
+    8
 }
 fn default_true() -> bool {
     true
@@ -51,13 +79,32 @@ impl Profile {
             .iter()
             .map(|m| MonitorConfig {
                 name: m.name.clone(),
-                resolution: Some(m.resolution.to_string()),
-                refresh_rate: Some(m.refresh_rate),
-                position: [m.position.x, m.position.y],
-                scale: m.scale,
+                description: Some(m.description.clone()).filter(|d| !d.is_empty()),
+                resolution: Some(match m.mode_spec.as_ref().filter(|s| s.is_symbolic()) {
+                    Some(spec) => spec.to_string(),
+                    None => m.resolution.to_string(),
+                }),
+                refresh_rate: Some(m.refresh_rate).filter(|_| {
+                    !m.mode_spec
+                        .as_ref()
+                        .is_some_and(|s| s.leaves_refresh_rate())
+                }),
+                position: m
+                    .position_spec
+                    .clone()
+                    .filter(|s| s.is_symbolic())
+                    .unwrap_or(PositionSpec::Explicit(m.position.clone())),
+                scale: Some(m.scale),
                 transform: m.transform.to_hyprland_str().to_string(),
                 enabled: m.enabled,
                 primary: m.primary,
+                mirror_of: m.mirror_of.clone(),
+                vrr: m.vrr,
+                bitdepth: m.bitdepth,
+                color_mode: m.color_mode,
+                sdr_brightness: m.sdr_brightness,
+                sdr_saturation: m.sdr_saturation,
+                reserved: m.reserved.filter(|r| !r.is_empty()),
             })
             .collect();
         Profile {
@@ -69,49 +116,236 @@ impl Profile {
         }
     }
 
+    /// Build a profile from parsed `monitor=` rules; a later rule for the same output wins.
+    /// Catch-all rules are skipped; symbolic modes and positions are kept.
+    /// `addreserved` rules attach their inset to the output's own rule; a `monitorv2`
+    /// block carries its own.
+    pub fn from_rules(name: String, description: Option<String>, rules: &[MonitorRule]) -> Self {
+        let mut latest: Vec<&MonitorRule> = Vec::new();
+        let mut reserved: Vec<(&OutputSelector, Reserved)> = Vec::new();
+        for rule in rules {
+            if rule.output == OutputSelector::Any {
+                continue;
+            }
+            if rule.addreserved {
+                reserved.retain(|(o, _)| **o != rule.output);
+                reserved.extend(rule.reserved.map(|r| (&rule.output, r)));
+                continue;
+            }
+            match latest.iter().position(|r| r.output == rule.output) {
+                Some(i) => latest[i] = rule,
+                None => latest.push(rule),
+            }
+        }
+        let monitors = latest
+            .into_iter()
+            .map(|rule| {
+                let (name, description) = match &rule.output {
+                    OutputSelector::Name(n) => (n.clone(), None),
+                    OutputSelector::Description(d) => (String::new(), Some(d.clone())),
+                    OutputSelector::Any => unreachable!(),
+                };
+                let (resolution, refresh_rate) = match &rule.mode {
+                    ModeSpec::Explicit {
+                        resolution,
+                        refresh_rate,
+                    } => (Some(resolution.to_string()), *refresh_rate),
+                    spec => (Some(spec.to_string()), None),
+                };
+                MonitorConfig {
+                    name,
+                    description,
+                    resolution,
+                    refresh_rate,
+                    position: rule.position.clone(),
+                    scale: rule.scale,
+                    transform: rule.transform.to_hyprland_str().to_string(),
+                    enabled: !rule.disabled,
+                    primary: false,
+                    mirror_of: rule.mirror.clone(),
+                    vrr: rule.vrr,
+                    bitdepth: rule.bitdepth.unwrap_or(8),
+                    color_mode: rule.color_mode,
+                    sdr_brightness: rule.sdr_brightness,
+                    sdr_saturation: rule.sdr_saturation,
+                    reserved: reserved
+                        .iter()
+                        .find(|(o, _)| **o == rule.output)
+                        .map(|(_, r)| *r)
+                        .or(rule.reserved)
+                        .filter(|r| !r.is_empty()),
+                }
+            })
+            .collect();
+        let now = Utc::now().to_rfc3339();
+        Profile {
+            name,
+            description,
+            monitors,
+            created_at: Some(now.clone()),
+            modified_at: Some(now),
+        }
+    }
+
     /// Convert to Monitor list (requires merging with current monitor list for resolutions).
     pub fn to_monitors(&self, current: &[Monitor]) -> Vec<Monitor> {
-        self.monitors
+        self.match_monitors(current).monitors
+    }
+
+    /// Match profile entries to connected monitors, preferring the description and
+    /// falling back to the connector name. Matched monitors take the live connector name.
+    /// Descriptions match by prefix like Hyprland's `desc:`; longer descriptions are
+    /// matched first so a full description wins over a prefix of it.
+    pub fn match_monitors(&self, current: &[Monitor]) -> ProfileMatch {
+        let descs: Vec<Option<&str>> = self
+            .monitors
             .iter()
-            .filter_map(|mc| {
-                let cur = current.iter().find(|m| m.name == mc.name)?;
-                let (res, refresh): (Resolution, f32) = mc
-                    .resolution
-                    .as_ref()
-                    .and_then(|r| parse_res(r))
-                    .map(|(res, hz)| (res, mc.refresh_rate.unwrap_or(hz)))
-                    .unwrap_or((cur.resolution.clone(), mc.refresh_rate.unwrap_or(cur.refresh_rate)));
-                Some(Monitor {
-                    name: mc.name.clone(),
-                    description: cur.description.clone(),
-                    position: Position {
-                        x: mc.position[0],
-                        y: mc.position[1],
-                    },
-                    resolution: res,
-                    available_resolutions: cur.available_resolutions.clone(),
-                    refresh_rate: refresh,
-                    available_refresh_rates: cur.available_refresh_rates.clone(),
-                    scale: mc.scale,
-                    transform: Transform::from_hyprland_str(&mc.transform),
-                    enabled: mc.enabled,
-                    primary: mc.primary,
+            .map(|mc| mc.description.as_deref().filter(|d| !d.is_empty()))
+            .collect();
+        let mut order: Vec<usize> = (0..self.monitors.len()).collect();
+        order.sort_by_key(|&j| std::cmp::Reverse(descs[j].map_or(0, str::len)));
+        let mut used = vec![false; current.len()];
+        let mut found: Vec<Option<usize>> = vec![None; self.monitors.len()];
+        for j in order {
+            let (mc, desc) = (&self.monitors[j], descs[j]);
+            let free = |i: &usize| !used[*i];
+            let by_desc = |name_too: bool| {
+                (0..current.len()).filter(free).find(|&i| {
+                    desc.is_some_and(|d| current[i].description.starts_with(d))
+                        && (!name_too || current[i].name == mc.name)
                 })
-            })
-            .collect()
+            };
+            found[j] = by_desc(true).or_else(|| by_desc(false)).or_else(|| {
+                (0..current.len())
+                    .filter(free)
+                    .find(|&i| current[i].name == mc.name)
+            });
+            if let Some(i) = found[j] {
+                used[i] = true;
+            }
+        }
+        let mut monitors = Vec::new();
+        let mut unmatched = Vec::new();
+        let mut matched_names = Vec::new();
+        for ((mc, desc), found) in self.monitors.iter().zip(&descs).zip(found) {
+            match found {
+                Some(i) => {
+                    monitors.push(mc.to_monitor(&current[i]));
+                    matched_names.push(Some(current[i].name.clone()));
+                }
+                None => {
+                    unmatched.push(desc.unwrap_or(&mc.name).to_string());
+                    matched_names.push(None);
+                }
+            }
+        }
+        // Mirror sources are saved by connector name; follow them to the live connector.
+        let renamed: Vec<(String, String)> = self
+            .monitors
+            .iter()
+            .zip(&matched_names)
+            .filter_map(|(mc, live)| live.as_ref().map(|l| (mc.name.clone(), l.clone())))
+            .collect();
+        for m in monitors.iter_mut() {
+            if let Some(source) = m.mirror_of.as_mut() {
+                if let Some((_, live)) = renamed.iter().find(|(saved, _)| saved == source) {
+                    *source = live.clone();
+                }
+            }
+        }
+        resolve_auto_positions(&mut monitors);
+        ProfileMatch {
+            monitors,
+            unmatched,
+        }
     }
 }
 
+impl MonitorConfig {
+    /// Build a Monitor from this entry on top of the connected monitor `cur`.
+    fn to_monitor(&self, cur: &Monitor) -> Monitor {
+        // Kept when Hyprland picks the mode, or the refresh rate for a bare `WxH`.
+        let mode_spec = self
+            .resolution
+            .as_deref()
+            .and_then(ModeSpec::parse)
+            .filter(|s| {
+                s.is_symbolic() || (s.leaves_refresh_rate() && self.refresh_rate.is_none())
+            });
+        let (res, refresh): (Resolution, f32) = match &mode_spec {
+            Some(spec) => spec
+                .resolve(&cur.available_modes)
+                .unwrap_or((cur.resolution.clone(), cur.refresh_rate)),
+            None => self
+                .resolution
+                .as_ref()
+                .and_then(|r| parse_res(r))
+                .map(|(res, hz)| (res, self.refresh_rate.unwrap_or(hz)))
+                .unwrap_or((
+                    cur.resolution.clone(),
+                    self.refresh_rate.unwrap_or(cur.refresh_rate),
+                )),
+        };
+        let (position, position_spec) = match &self.position {
+            PositionSpec::Explicit(p) => (p.clone(), None),
+            // Resolved against the other monitors by `resolve_auto_positions`.
+            spec => (Position::default(), Some(spec.clone())),
+        };
+        Monitor {
+            name: cur.name.clone(),
+            description: cur.description.clone(),
+            position,
+            resolution: res,
+            available_resolutions: cur.available_resolutions.clone(),
+            refresh_rate: refresh,
+            available_refresh_rates: cur.available_refresh_rates.clone(),
+            available_modes: cur.available_modes.clone(),
+            scale: self.scale.unwrap_or(cur.scale),
+            transform: Transform::from_hyprland_str(&self.transform),
+            enabled: self.enabled,
+            primary: self.primary,
+            mode_spec,
+            position_spec,
+            mirror_of: self.mirror_of.clone(),
+            vrr: self.vrr,
+            bitdepth: self.bitdepth,
+            color_mode: self.color_mode,
+            sdr_brightness: in_range(self.sdr_brightness, SDR_BRIGHTNESS_RANGE, "sdr_brightness"),
+            sdr_saturation: in_range(self.sdr_saturation, SDR_SATURATION_RANGE, "sdr_saturation"),
+            reserved: self.reserved,
+        }
+    }
+}
+
+/// Drop a saved value outside `range` (Hyprland would reject the whole rule).
+fn in_range(value: Option<f32>, range: std::ops::RangeInclusive<f32>, key: &str) -> Option<f32> {
+    value.filter(|v| {
+        let ok = range.contains(v);
+        if !ok {
+            tracing::warn!("Ignoring {} {} outside {:?}", key, v, range);
+        }
+        ok
+    })
+}
+
+/// Result of matching a profile against the connected monitors.
+#[derive(Clone, Debug, Default)]
+pub struct ProfileMatch {
+    /// Monitors configured by the profile, named after their current connector
+    pub monitors: Vec<Monitor>,
+    /// Profile entries with no connected monitor (description, or name if none)
+    pub unmatched: Vec<String>,
+}
+
+/// Parse "WxH" or "WxH@HZ" (refresh defaults to 60 Hz).
 fn parse_res(s: &str) -> Option<(Resolution, f32)> {
-    let (res, hz) = s.split_once('@').map(|(a, b)| (a, b.parse().ok()))?;
-    let (w, h) = res.split_once('x')?;
-    Some((
-        Resolution {
-            width: w.parse().ok()?,
-            height: h.parse().ok()?,
-        },
-        hz.unwrap_or(60.0),
-    ))
+    match ModeSpec::parse(s)? {
+        ModeSpec::Explicit {
+            resolution,
+            refresh_rate,
+        } => Some((resolution, refresh_rate.unwrap_or(60.0))),
+        _ => None,
+    }
 }
 
 /// Load profile from path.
@@ -139,3 +373,44 @@ pub fn list_profiles(dir: &Path) -> Vec<String> {
         .filter_map(|e| e.path().file_stem().and_then(|s| s.to_str().map(String::from)))
         .collect()
 }
+
+/// Load every profile in a directory as (file stem, profile), sorted by file stem.
+/// Unreadable profiles are skipped with a warning.
+pub fn load_profiles(dir: &Path) -> Vec<(String, Profile)> {
+    let mut names = list_profiles(dir);
+    names.sort();
+    names
+        .into_iter()
+        .filter_map(|n| {
+            let path = dir.join(format!("{}.toml", n));
+            match load_profile(&path) {
+                Ok(p) => Some((n, p)),
+                Err(e) => {
+                    tracing::warn!("Skipping profile {}: {}", path.display(), e);
+                    None
+                }
+            }
+        })
+        .collect()
+}
+
+/// Check that a profile name can be used as a file name in the profiles directory.
+pub fn validate_profile_name(name: &str) -> Result<(), String> {
+    let name = name.trim();
+    if name.is_empty() {
+        return Err("Name must not be empty".to_string());
+    }
+    if name.starts_with('.') {
+        return Err("Name must not start with '.'".to_string());
+    }
+    if name.len() > 64 {
+        return Err("Name must be at most 64 characters".to_string());
+    }
+    if let Some(c) = name
+        .chars()
+        .find(|c| !(c.is_alphanumeric() || matches!(c, '-' | '_' | '.' | ' ')))
+    {
+        return Err(format!("Invalid character '{}' in name", c));
+    }
+    Ok(())
+}
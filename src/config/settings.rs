@@ -1,7 +1,9 @@
 //! Application settings (themes, defaults).
 
+use crate::hyprland::{expand_home, ConfigSyntax, PersistMode, PersistTarget};
 use ratatui::style::Color;
 use serde::{Deserialize, Serialize};
+use std::path::{Path, PathBuf};
 
 /// Application color theme (Omarchy-compatible).
 #[derive(Clone, Debug)]
@@ -103,7 +105,7 @@ impl ThemeConfig {
 }
 
 /// General app settings.
-#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+#[derive(Clone, Debug, Serialize, Deserialize)]
 pub struct GeneralSettings {
     #[serde(default)]
     pub auto_apply: bool,
@@ -111,6 +113,58 @@ pub struct GeneralSettings {
     pub preview_timeout: u64,
     #[serde(default = "default_scale_step")]
     pub scale_step: f32,
+    /// Pixels moved per arrow key in move mode
+    #[serde(default = "default_move_step")]
+    pub move_step: i32,
+    /// Pixels moved per Shift+arrow key in move mode
+    #[serde(default = "default_move_step_large")]
+    pub move_step_large: i32,
+    /// Export `monitor=desc:...` rules instead of connector names
+    #[serde(default)]
+    pub export_by_description: bool,
+    /// Generated rule syntax: `legacy` (`monitor=`) or `v2` (`monitorv2` blocks)
+    #[serde(default)]
+    pub export_syntax: ConfigSyntax,
+    /// How `x` persists rules: `source` (monitors.conf) or `managed` (block in hyprland.conf)
+    #[serde(default)]
+    pub persist_mode: PersistMode,
+    /// Hyprland config file (default ~/.config/hypr/hyprland.conf)
+    #[serde(default)]
+    pub hyprland_conf: Option<PathBuf>,
+    /// File written in `source` mode (default ~/.config/hypr/monitors.conf)
+    #[serde(default)]
+    pub monitors_conf: Option<PathBuf>,
+}
+
+impl GeneralSettings {
+    /// Files written when persisting, with configured paths overriding the defaults.
+    pub fn persist_target(&self) -> PersistTarget {
+        let mut target = PersistTarget::new(self.persist_mode);
+        if let Some(p) = &self.hyprland_conf {
+            target.hyprland_conf = expand_home(&p.to_string_lossy());
+        }
+        if let Some(p) = &self.monitors_conf {
+            target.monitors_conf = expand_home(&p.to_string_lossy());
+        }
+        target
+    }
+}
+
+impl Default for GeneralSettings {
+    fn default() -> Self {
+        Self {
+            auto_apply: false,
+            preview_timeout: default_preview_timeout(),
+            scale_step: default_scale_step(),
+            move_step: default_move_step(),
+            move_step_large: default_move_step_large(),
+            export_by_description: false,
+            export_syntax: ConfigSyntax::default(),
+            persist_mode: PersistMode::default(),
+            hyprland_conf: None,
+            monitors_conf: None,
+        }
+    }
 }
 
 fn default_preview_timeout() -> u64 {
@@ -119,6 +173,12 @@ fn default_preview_timeout() -> u64 {
 fn default_scale_step() -> f32 {
     0.25
 }
+fn default_move_step() -> i32 {
+    10
+}
+fn default_move_step_large() -> i32 {
+    100
+}
 
 /// Keybindings (optional overrides).
 #[derive(Clone, Debug, Default, Serialize, Deserialize)]
@@ -139,3 +199,12 @@ pub struct AppConfig {
     #[serde(default)]
     pub keybindings: KeybindingsConfig,
 }
+
+/// Load config.toml; a missing file yields the defaults.
+pub fn load_config(path: &Path) -> anyhow::Result<AppConfig> {
+    if !path.exists() {
+        return Ok(AppConfig::default());
+    }
+    let s = std::fs::read_to_string(path)?;
+    Ok(toml::from_str(&s)?)
+}
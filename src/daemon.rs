@@ -0,0 +1,142 @@
+//! Profile auto-switching daemon (kanshi-style).
+//!
+//! Watches Hyprland monitor hotplug events, fingerprints the connected outputs and
+//! applies the saved profile that matches them best.
+
+use crate::config::{self, Profile};
+use crate::hyprland::{validate_layout, Monitor, MonitorBackend, Severity};
+use anyhow::{Context, Result};
+use std::path::PathBuf;
+use std::sync::mpsc::RecvTimeoutError;
+use std::time::Duration;
+use tracing::{debug, info, warn};
+
+/// Daemon settings.
+#[derive(Clone, Debug)]
+pub struct DaemonOptions {
+    /// Directory with saved profiles
+    pub profiles_dir: PathBuf,
+    /// Quiet period after the last hotplug event before re-evaluating
+    pub debounce: Duration,
+    /// Only log what would be applied
+    pub dry_run: bool,
+}
+
+/// Connected-output fingerprint: sorted connector names (disabled outputs included).
+pub fn fingerprint(monitors: &[Monitor]) -> Vec<String> {
+    let mut names: Vec<String> = monitors.iter().map(|m| m.name.clone()).collect();
+    names.sort();
+    names
+}
+
+/// How well a profile fits the connected outputs: None if one of its monitors is not
+/// connected (matched by description, then connector name), otherwise the number of
+/// connected outputs it configures.
+pub fn profile_score(profile: &Profile, connected: &[Monitor]) -> Option<usize> {
+    let matched = profile.match_monitors(connected);
+    if !matched.unmatched.is_empty() || matched.monitors.is_empty() {
+        return None;
+    }
+    Some(matched.monitors.len())
+}
+
+/// Best matching profile; ties go to the first profile in `profiles`.
+pub fn best_profile<'a>(profiles: &'a [Profile], connected: &[Monitor]) -> Option<&'a Profile> {
+    profiles
+        .iter()
+        .filter_map(|p| profile_score(p, connected).map(|s| (s, p)))
+        .fold(None, |best: Option<(usize, &Profile)>, (s, p)| match best {
+            Some((bs, _)) if bs >= s => best,
+            _ => Some((s, p)),
+        })
+        .map(|(_, p)| p)
+}
+
+/// Apply the best profile for the current outputs once. Returns the chosen profile name,
+/// or None if nothing matches or the matching profile's layout has errors.
+pub fn apply_best(backend: &dyn MonitorBackend, opts: &DaemonOptions) -> Result<Option<String>> {
+    let connected = backend.get_monitors().context("Failed to get monitors")?;
+    let profiles: Vec<Profile> = config::load_profiles(&opts.profiles_dir)
+        .into_iter()
+        .map(|(_, p)| p)
+        .collect();
+    let Some(profile) = best_profile(&profiles, &connected) else {
+        info!("No profile matches outputs {:?}", fingerprint(&connected));
+        return Ok(None);
+    };
+    let monitors = profile.to_monitors(&connected);
+    let unconfigured: Vec<&str> = connected
+        .iter()
+        .filter(|c| !monitors.iter().any(|m| m.name == c.name))
+        .map(|c| c.name.as_str())
+        .collect();
+    if !unconfigured.is_empty() {
+        info!(
+            "Outputs not in profile {}: {:?}",
+            profile.name, unconfigured
+        );
+    }
+    let issues = validate_layout(&monitors);
+    if let Some(issue) = issues.iter().find(|i| i.severity == Severity::Error) {
+        warn!("Not applying profile {}: {}", profile.name, issue);
+        return Ok(None);
+    }
+    if opts.dry_run {
+        info!(
+            "Would apply profile {} ({} monitors)",
+            profile.name,
+            monitors.len()
+        );
+        for m in &monitors {
+            info!("  {}", crate::hyprland::monitor_rule(m));
+        }
+    } else {
+        info!("Applying profile {}", profile.name);
+        backend
+            .apply_all(&monitors)
+            .with_context(|| format!("Failed to apply profile {}", profile.name))?;
+    }
+    Ok(Some(profile.name.clone()))
+}
+
+/// Run until the event socket closes: apply on start and after each burst of hotplug events.
+pub fn run(backend: &dyn MonitorBackend, opts: &DaemonOptions) -> Result<()> {
+    let events = backend
+        .subscribe_events()
+        .context("Failed to subscribe to Hyprland events")?;
+    let mut last = backend.get_monitors().map(|m| fingerprint(&m)).ok();
+    if let Err(e) = apply_best(backend, opts) {
+        warn!("{:#}", e);
+    }
+    loop {
+        let Ok(first) = events.recv() else {
+            info!("Hyprland event socket closed, stopping");
+            return Ok(());
+        };
+        debug!("Monitor event: {:?}", first);
+        // Debounce: wait until events stop arriving for `debounce`.
+        loop {
+            match events.recv_timeout(opts.debounce) {
+                Ok(ev) => debug!("Monitor event: {:?}", ev),
+                Err(RecvTimeoutError::Timeout) => break,
+                Err(RecvTimeoutError::Disconnected) => break,
+            }
+        }
+        let current = match backend.get_monitors() {
+            Ok(m) => fingerprint(&m),
+            Err(e) => {
+                warn!("Failed to get monitors: {}", e);
+                continue;
+            }
+        };
+        if last.as_ref() == Some(&current) {
+            debug!("Outputs unchanged ({:?}), nothing to do", current);
+            continue;
+        }
+        info!("Connected outputs changed: {:?}", current);
+        last = Some(current);
+        if let Err(e) = apply_best(backend, opts) {
+            warn!("{:#}", e);
+        }
+    }
+}
@@ -1,5 +1,6 @@
 //! Event handling (keyboard, mouse, resize).
 
+use crate::hyprland::MonitorEvent;
 use crossterm::event::{self, Event as CrosstermEvent, KeyEvent, MouseEvent};
 use std::sync::mpsc;
 use std::time::Duration;
@@ -21,13 +22,15 @@ pub enum AppEvent {
 
 /// Event handler.
 pub struct EventHandler {
+    sender: mpsc::Sender<AppEvent>,
     receiver: mpsc::Receiver<AppEvent>,
 }
 
 impl EventHandler {
     /// Create new event handler; spawns a thread that polls crossterm and sends Tick periodically.
     pub fn new(tick_rate: Duration) -> Self {
-        let (tx, receiver) = mpsc::channel();
+        let (sender, receiver) = mpsc::channel();
+        let tx = sender.clone();
         std::thread::spawn(move || loop {
             if event::poll(tick_rate).unwrap_or(false) {
                 if let Ok(ev) = event::read() {
@@ -47,7 +50,19 @@ impl EventHandler {
             }
             let _ = tx.send(AppEvent::Tick);
         });
-        Self { receiver }
+        Self { sender, receiver }
+    }
+
+    /// Forward Hyprland monitor hotplug events as `AppEvent::MonitorChange`.
+    pub fn forward_monitor_events(&self, events: mpsc::Receiver<MonitorEvent>) {
+        let tx = self.sender.clone();
+        std::thread::spawn(move || {
+            for _ in events {
+                if tx.send(AppEvent::MonitorChange).is_err() {
+                    break;
+                }
+            }
+        });
     }
 
     /// Receive next event (blocking).
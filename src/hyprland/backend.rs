@@ -0,0 +1,30 @@
+//! Monitor backend abstraction (live Hyprland or a test double).
+
+use crate::hyprland::ipc::{ApplyError, MonitorEvent};
+use crate::hyprland::monitor::{Mode, Monitor};
+use anyhow::{Context, Result};
+use std::sync::mpsc;
+
+/// Source of monitor state and target for monitor rules.
+///
+/// `HyprlandClient` talks to a running compositor; tests point a client at a fake socket.
+pub trait MonitorBackend {
+    /// Get all monitors with their current configuration.
+    fn get_monitors(&self) -> Result<Vec<Monitor>>;
+
+    /// Apply the rules for all monitors in one step.
+    fn apply_all(&self, monitors: &[Monitor]) -> std::result::Result<(), ApplyError>;
+
+    /// Subscribe to monitor hotplug events.
+    fn subscribe_events(&self) -> Result<mpsc::Receiver<MonitorEvent>>;
+
+    /// Get available modes for a specific monitor.
+    fn get_available_modes(&self, monitor_name: &str) -> Result<Vec<Mode>> {
+        let monitors = self.get_monitors()?;
+        let monitor = monitors
+            .into_iter()
+            .find(|m| m.name == monitor_name)
+            .with_context(|| format!("Monitor not found: {}", monitor_name))?;
+        Ok(monitor.available_modes)
+    }
+}
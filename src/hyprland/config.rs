@@ -1,6 +1,11 @@
-//! Hyprland config file generation.
+//! Hyprland config file generation and parsing.
 
-use crate::hyprland::monitor::Monitor;
+use crate::hyprland::monitor::{
+    parse_sdr_value, ColorMode, ModeSpec, Monitor, PositionSpec, Reserved, Transform, Vrr,
+    SDR_BRIGHTNESS_RANGE, SDR_SATURATION_RANGE,
+};
+use anyhow::{Context, Result};
+use std::path::{Path, PathBuf};
 
 /// Workspace-to-monitor assignment (optional).
 #[derive(Clone, Debug, Default)]
@@ -9,31 +14,184 @@ pub struct WorkspaceConfig {
     pub assignments: Vec<(u32, String, bool)>,
 }
 
-/// One monitor line: name,res@hz,pos,scale
-fn monitor_line(m: &Monitor) -> String {
+/// Syntax of generated monitor rules.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ConfigSyntax {
+    /// `monitor=name,mode,position,scale[,key,value]...`
+    #[default]
+    Legacy,
+    /// `monitorv2 { output = ..., mode = ..., ... }` blocks
+    V2,
+}
+
+impl std::str::FromStr for ConfigSyntax {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "legacy" => Ok(ConfigSyntax::Legacy),
+            "v2" | "monitorv2" => Ok(ConfigSyntax::V2),
+            _ => Err(format!("unknown syntax '{}' (expected legacy or v2)", s)),
+        }
+    }
+}
+
+/// Options for generated config files.
+#[derive(Clone, Debug, Default)]
+pub struct ExportOptions {
+    /// Select monitors with `desc:<description>` instead of the connector name
+    pub by_description: bool,
+    /// Legacy `monitor=` lines or `monitorv2` blocks
+    pub syntax: ConfigSyntax,
+}
+
+/// Monitor rule as used by `keyword monitor` and `monitor=`:
+/// name,res@hz,pos,scale[,transform,N][,mirror,NAME][,bitdepth,10][,vrr,N][,cm,NAME]
+/// [,sdrbrightness,F][,sdrsaturation,F], or name,disable.
+pub fn monitor_rule(m: &Monitor) -> String {
+    rule_for_output(m, &m.name)
+}
+
+/// Output selector for a config line: connector name or `desc:...`.
+fn output_selector(m: &Monitor, options: &ExportOptions) -> String {
+    if options.by_description && !m.description.is_empty() {
+        format!("desc:{}", m.description)
+    } else {
+        m.name.clone()
+    }
+}
+
+/// Settings of an enabled monitor in rule order: mode, position, scale, then optional keys.
+fn rule_settings(m: &Monitor) -> Vec<(&'static str, String)> {
+    let mode = match &m.mode_spec {
+        Some(spec) if spec.leaves_refresh_rate() => spec.to_string(),
+        _ => format!("{}@{:.2}", m.resolution, m.refresh_rate),
+    };
+    let position = match &m.position_spec {
+        Some(spec) if spec.is_symbolic() => spec.to_string(),
+        _ => format!("{}x{}", m.position.x, m.position.y),
+    };
+    let mut settings = vec![
+        ("mode", mode),
+        ("position", position),
+        ("scale", m.scale.to_string()),
+    ];
+    if m.transform != Transform::Normal {
+        settings.push(("transform", m.transform.to_hyprland_str().to_string()));
+    }
+    if let Some(source) = &m.mirror_of {
+        settings.push(("mirror", source.clone()));
+    }
+    if m.bitdepth == 10 {
+        settings.push(("bitdepth", "10".to_string()));
+    }
+    if let Some(vrr) = m.vrr {
+        settings.push(("vrr", vrr.to_hyprland_str().to_string()));
+    }
+    if let Some(cm) = m.color_mode {
+        settings.push(("cm", cm.to_hyprland_str().to_string()));
+    }
+    if let Some(v) = m.sdr_brightness {
+        settings.push(("sdrbrightness", v.to_string()));
+    }
+    if let Some(v) = m.sdr_saturation {
+        settings.push(("sdrsaturation", v.to_string()));
+    }
+    settings
+}
+
+fn rule_for_output(m: &Monitor, output: &str) -> String {
+    if !m.enabled {
+        return format!("{},disable", output);
+    }
+    let mut rule = output.to_string();
+    for (i, (key, value)) in rule_settings(m).into_iter().enumerate() {
+        // Mode, position and scale are positional; later settings are key,value pairs.
+        if i >= 3 {
+            rule.push(',');
+            rule.push_str(key);
+        }
+        rule.push(',');
+        rule.push_str(&value);
+    }
+    rule
+}
+
+/// Reserved area rule sent after the monitor rule: name,addreserved,T,B,L,R.
+/// An empty inset is sent as 0,0,0,0 because a monitor rule alone does not reset it.
+/// None for disabled monitors and monitors without a reserved area.
+pub fn reserved_rule(m: &Monitor) -> Option<String> {
+    reserved_for_output(m, &m.name)
+}
+
+fn reserved_for_output(m: &Monitor, output: &str) -> Option<String> {
+    let reserved = m.reserved.filter(|_| m.enabled)?;
+    Some(format!("{},addreserved,{}", output, reserved))
+}
+
+/// One config line: monitor=<rule>
+fn monitor_line(m: &Monitor, options: &ExportOptions) -> String {
     format!(
-        "monitor={},{}@{:.2},{}x{},{}",
-        m.name,
-        m.resolution,
-        m.refresh_rate,
-        m.position.x,
-        m.position.y,
-        m.scale
+        "monitor={}",
+        rule_for_output(m, &output_selector(m, options))
     )
 }
 
+/// `monitor=...,addreserved,...` line, if the monitor has a non-empty reserved area.
+fn reserved_line(m: &Monitor, options: &ExportOptions) -> Option<String> {
+    if m.reserved.is_none_or(|r| r.is_empty()) {
+        return None;
+    }
+    reserved_for_output(m, &output_selector(m, options)).map(|r| format!("monitor={}", r))
+}
+
+/// One `monitorv2 { ... }` block; a non-empty reserved area is its `addreserved` key.
+fn monitorv2_block(m: &Monitor, options: &ExportOptions) -> String {
+    let mut block = format!("monitorv2 {{\n  output = {}\n", output_selector(m, options));
+    if m.enabled {
+        for (key, value) in rule_settings(m) {
+            block.push_str(&format!("  {} = {}\n", key, value));
+        }
+        if let Some(r) = m.reserved.filter(|r| !r.is_empty()) {
+            block.push_str(&format!(
+                "  addreserved = {}, {}, {}, {}\n",
+                r.top, r.bottom, r.left, r.right
+            ));
+        }
+    } else {
+        block.push_str("  disabled = true\n");
+    }
+    block.push('}');
+    block
+}
+
 /// Generate Hyprland monitor config block.
 pub fn generate_config(monitors: &[Monitor]) -> String {
+    generate_config_with(monitors, &ExportOptions::default())
+}
+
+/// Generate Hyprland monitor config block with export options.
+pub fn generate_config_with(monitors: &[Monitor], options: &ExportOptions) -> String {
     let mut lines = vec![
         "# Generated by hypr-monitor-tui".to_string(),
         "# https://github.com/Ninso112/hypr-monitor-tui".to_string(),
         String::new(),
     ];
-    for m in monitors {
-        if !m.enabled {
-            continue;
+    match options.syntax {
+        ConfigSyntax::Legacy => {
+            for m in monitors {
+                lines.push(monitor_line(m, options));
+                lines.extend(reserved_line(m, options));
+            }
+        }
+        ConfigSyntax::V2 => {
+            let blocks: Vec<String> = monitors
+                .iter()
+                .map(|m| monitorv2_block(m, options))
+                .collect();
+            lines.push(blocks.join("\n\n"));
         }
-        lines.push(monitor_line(m));
     }
     lines.join("\n")
 }
@@ -54,10 +212,287 @@ pub fn generate_config_with_workspaces(
     out
 }
 
+/// Output a `monitor=` rule applies to.
+#[derive(Clone, Debug, PartialEq, Default)]
+pub enum OutputSelector {
+    /// Empty name: fallback rule for any monitor without its own rule
+    #[default]
+    Any,
+    /// Connector name, e.g. "DP-1"
+    Name(String),
+    /// `desc:` selector (make, model, serial)
+    Description(String),
+}
+
+/// One parsed `monitor=` rule.
+/// The default is Hyprland's for an omitted field: preferred mode, auto position and scale.
+#[derive(Clone, Debug, PartialEq, Default)]
+pub struct MonitorRule {
+    pub output: OutputSelector,
+    pub mode: ModeSpec,
+    pub position: PositionSpec,
+    /// None for `auto` scale
+    pub scale: Option<f32>,
+    pub transform: Transform,
+    /// Output mirrored by this one
+    pub mirror: Option<String>,
+    /// 8 or 10
+    pub bitdepth: Option<u8>,
+    pub vrr: Option<Vrr>,
+    pub color_mode: Option<ColorMode>,
+    pub sdr_brightness: Option<f32>,
+    pub sdr_saturation: Option<f32>,
+    pub reserved: Option<Reserved>,
+    /// `name,addreserved,T,B,L,R`: only sets `reserved` for an output configured elsewhere
+    pub addreserved: bool,
+    pub disabled: bool,
+}
+
+impl MonitorRule {
+    fn disabled(output: OutputSelector) -> Self {
+        Self {
+            output,
+            disabled: true,
+            ..Default::default()
+        }
+    }
+
+    fn addreserved(output: OutputSelector, reserved: Reserved) -> Self {
+        Self {
+            output,
+            reserved: Some(reserved),
+            addreserved: true,
+            ..Default::default()
+        }
+    }
+}
+
+/// Parse the value of a `monitor=` line:
+/// name,mode,position,scale[,transform,N][,mirror,NAME][,bitdepth,N][,vrr,N], name,disable,
+/// or name,addreserved,T,B,L,R.
+pub fn parse_monitor_rule(value: &str) -> Result<MonitorRule, String> {
+    let fields: Vec<&str> = value.split(',').map(str::trim).collect();
+    let output = parse_output(fields[0]);
+    match fields.get(1) {
+        Some(&"disable") | Some(&"disabled") => return Ok(MonitorRule::disabled(output)),
+        Some(&"addreserved") => return parse_addreserved(output, &fields[2..]),
+        Some(_) if fields.len() >= 4 => {}
+        _ => return Err(format!("Expected name,mode,position,scale in '{}'", value)),
+    }
+    let mode = ModeSpec::parse(fields[1]).ok_or_else(|| format!("Invalid mode '{}'", fields[1]))?;
+    let position = PositionSpec::parse(fields[2])
+        .ok_or_else(|| format!("Invalid position '{}'", fields[2]))?;
+    let scale = match fields[3] {
+        "auto" => None,
+        s => Some(s.parse().map_err(|_| format!("Invalid scale '{}'", s))?),
+    };
+    let mut rule = MonitorRule {
+        output,
+        mode,
+        position,
+        scale,
+        ..Default::default()
+    };
+    let mut rest = fields[4..].iter();
+    while let Some(&key) = rest.next() {
+        let value = rest
+            .next()
+            .copied()
+            .ok_or_else(|| format!("Missing value for '{}'", key))?;
+        rule.set_option(key, value)?;
+    }
+    Ok(rule)
+}
+
+impl MonitorRule {
+    /// Apply an optional `key,value` setting shared by both rule syntaxes.
+    fn set_option(&mut self, key: &str, value: &str) -> Result<(), String> {
+        let invalid = || format!("Invalid {} '{}'", key, value);
+        match key {
+            "transform" => {
+                if !matches!(value, "0" | "1" | "2" | "3" | "4" | "5" | "6" | "7") {
+                    return Err(invalid());
+                }
+                self.transform = Transform::from_hyprland_str(value);
+            }
+            "mirror" => self.mirror = Some(value.to_string()),
+            "bitdepth" => match value {
+                "8" | "10" => self.bitdepth = value.parse().ok(),
+                _ => return Err(invalid()),
+            },
+            "vrr" => self.vrr = Some(Vrr::from_hyprland_str(value).ok_or_else(invalid)?),
+            "cm" => {
+                self.color_mode = Some(ColorMode::from_hyprland_str(value).ok_or_else(invalid)?)
+            }
+            "sdrbrightness" => {
+                self.sdr_brightness = Some(parse_sdr_value(key, value, SDR_BRIGHTNESS_RANGE)?)
+            }
+            "sdrsaturation" => {
+                self.sdr_saturation = Some(parse_sdr_value(key, value, SDR_SATURATION_RANGE)?)
+            }
+            _ => tracing::debug!("Ignoring monitor rule option '{}'", key),
+        }
+        Ok(())
+    }
+}
+
+fn parse_addreserved(output: OutputSelector, fields: &[&str]) -> Result<MonitorRule, String> {
+    let reserved = parse_reserved(fields, "name,addreserved,TOP,BOTTOM,LEFT,RIGHT")?;
+    Ok(MonitorRule::addreserved(output, reserved))
+}
+
+/// Parse the four `addreserved` values (top, bottom, left, right); `usage` names the syntax.
+fn parse_reserved(fields: &[&str], usage: &str) -> Result<Reserved, String> {
+    let values: Vec<u32> = fields
+        .iter()
+        .map(|f| {
+            f.trim()
+                .parse()
+                .map_err(|_| format!("Invalid addreserved value '{}'", f))
+        })
+        .collect::<Result<_, _>>()?;
+    let [top, bottom, left, right] = values[..] else {
+        return Err(format!("Expected {}", usage));
+    };
+    Ok(Reserved {
+        top,
+        bottom,
+        left,
+        right,
+    })
+}
+
+fn parse_output(s: &str) -> OutputSelector {
+    match s {
+        "" => OutputSelector::Any,
+        f => match f.strip_prefix("desc:") {
+            Some(d) => OutputSelector::Description(d.trim().to_string()),
+            None => OutputSelector::Name(f.to_string()),
+        },
+    }
+}
+
+/// Parse the `key = value` pairs of a `monitorv2 { ... }` block.
+pub fn parse_monitorv2_block(pairs: &[(String, String)]) -> Result<MonitorRule, String> {
+    let get = |key: &str| {
+        pairs
+            .iter()
+            .rev()
+            .find(|(k, _)| k == key)
+            .map(|(_, v)| v.as_str())
+    };
+    let output = parse_output(get("output").ok_or("monitorv2 block without output")?);
+    if get("disabled").is_some_and(|v| matches!(v, "true" | "1" | "yes" | "on")) {
+        return Ok(MonitorRule::disabled(output));
+    }
+    let mode = get("mode").unwrap_or("preferred");
+    let position = get("position").unwrap_or("auto");
+    let mut rule = MonitorRule {
+        output,
+        mode: ModeSpec::parse(mode).ok_or_else(|| format!("Invalid mode '{}'", mode))?,
+        position: PositionSpec::parse(position)
+            .ok_or_else(|| format!("Invalid position '{}'", position))?,
+        scale: match get("scale") {
+            None | Some("auto") => None,
+            Some(s) => Some(s.parse().map_err(|_| format!("Invalid scale '{}'", s))?),
+        },
+        ..Default::default()
+    };
+    for (key, value) in pairs {
+        match key.as_str() {
+            "output" | "mode" | "position" | "scale" | "disabled" => {}
+            "addreserved" => {
+                let fields: Vec<&str> = value.split(',').collect();
+                rule.reserved = Some(parse_reserved(&fields, "addreserved = T, B, L, R")?);
+            }
+            _ => rule.set_option(key, value)?,
+        }
+    }
+    Ok(rule)
+}
+
+/// Read the `monitor=` rules and `monitorv2` blocks of a Hyprland config,
+/// following `source =` includes.
+/// Unparsable rules are skipped with a warning.
+pub fn read_monitor_rules(path: &Path) -> Result<Vec<MonitorRule>> {
+    let mut rules = Vec::new();
+    let mut visited = Vec::new();
+    read_rules_into(path, &mut rules, &mut visited)?;
+    Ok(rules)
+}
+
+fn read_rules_into(
+    path: &Path,
+    rules: &mut Vec<MonitorRule>,
+    visited: &mut Vec<PathBuf>,
+) -> Result<()> {
+    let canonical = path.canonicalize().unwrap_or_else(|_| path.to_path_buf());
+    if visited.contains(&canonical) {
+        return Ok(());
+    }
+    visited.push(canonical);
+    let content = std::fs::read_to_string(path)
+        .with_context(|| format!("Failed to read {}", path.display()))?;
+    let dir = path.parent().unwrap_or(Path::new("."));
+    // Open `monitorv2 {` block: starting line and its key = value pairs.
+    let mut block: Option<(usize, Vec<(String, String)>)> = None;
+    for (n, line) in content.lines().enumerate() {
+        let line = line.split('#').next().unwrap_or("").trim();
+        if let Some((start, pairs)) = block.as_mut() {
+            if line == "}" {
+                match parse_monitorv2_block(pairs) {
+                    Ok(rule) => rules.push(rule),
+                    Err(e) => tracing::warn!("{}:{}: {}", path.display(), *start + 1, e),
+                }
+                block = None;
+            } else if let Some((key, value)) = line.split_once('=') {
+                pairs.push((key.trim().to_string(), value.trim().to_string()));
+            }
+            continue;
+        }
+        if line
+            .strip_prefix("monitorv2")
+            .is_some_and(|rest| rest.trim() == "{")
+        {
+            block = Some((n, Vec::new()));
+            continue;
+        }
+        let Some((key, value)) = line.split_once('=') else {
+            continue;
+        };
+        match key.trim() {
+            "monitor" => match parse_monitor_rule(value.trim()) {
+                Ok(rule) => rules.push(rule),
+                Err(e) => tracing::warn!("{}:{}: {}", path.display(), n + 1, e),
+            },
+            "source" => {
+                let included = crate::hyprland::expand_home(value.trim());
+                let included = if included.is_relative() {
+                    dir.join(included)
+                } else {
+                    included
+                };
+                if let Err(e) = read_rules_into(&included, rules, visited) {
+                    tracing::warn!("{}:{}: {:#}", path.display(), n + 1, e);
+                }
+            }
+            _ => {}
+        }
+    }
+    if let Some((start, _)) = block {
+        tracing::warn!(
+            "{}:{}: unterminated monitorv2 block",
+            path.display(),
+            start + 1
+        );
+    }
+    Ok(())
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::hyprland::monitor::{Position, Resolution, Transform};
+    use crate::hyprland::monitor::{Position, Resolution};
 
     #[test]
     fn test_generate_config() {
@@ -72,14 +507,94 @@ mod tests {
             available_resolutions: vec![],
             refresh_rate: 144.0,
             available_refresh_rates: vec![],
+            available_modes: vec![],
             scale: 1.0,
             transform: Transform::Normal,
             enabled: true,
             primary: true,
+            mode_spec: None,
+            position_spec: None,
+            mirror_of: None,
+            vrr: None,
+            bitdepth: 8,
+            color_mode: None,
+            sdr_brightness: None,
+            sdr_saturation: None,
+            reserved: None,
         }];
         let s = generate_config(&monitors);
         assert!(s.contains("monitor=DP-1"));
         assert!(s.contains("2560x1440"));
         assert!(s.contains("144.00"));
     }
+
+    #[test]
+    fn test_parse_monitor_rule() {
+        let rule = parse_monitor_rule(
+            "desc:Dell Inc. U2720Q, preferred, auto-right, auto, transform, 1, vrr, 2",
+        )
+        .unwrap();
+        assert_eq!(
+            rule.output,
+            OutputSelector::Description("Dell Inc. U2720Q".to_string())
+        );
+        assert_eq!(rule.mode, ModeSpec::Preferred);
+        assert_eq!(rule.position, PositionSpec::AutoRight);
+        assert_eq!(rule.scale, None);
+        assert_eq!(rule.transform, Transform::Rotate90);
+        assert_eq!(rule.vrr, Some(Vrr::FullscreenOnly));
+
+        let rule = parse_monitor_rule("DP-1,2560x1440@143.91,1920x0,1.25,mirror,eDP-1,bitdepth,10")
+            .unwrap();
+        assert_eq!(rule.output, OutputSelector::Name("DP-1".to_string()));
+        assert_eq!(
+            rule.mode,
+            ModeSpec::Explicit {
+                resolution: Resolution {
+                    width: 2560,
+                    height: 1440
+                },
+                refresh_rate: Some(143.91),
+            }
+        );
+        assert_eq!(
+            rule.position,
+            PositionSpec::Explicit(Position { x: 1920, y: 0 })
+        );
+        assert_eq!(rule.scale, Some(1.25));
+        assert_eq!(rule.mirror.as_deref(), Some("eDP-1"));
+        assert_eq!(rule.bitdepth, Some(10));
+        assert!(parse_monitor_rule("DP-1,preferred,auto,1,vrr,3").is_err());
+        assert!(parse_monitor_rule("DP-1,preferred,auto,1,bitdepth,12").is_err());
+
+        let rule =
+            parse_monitor_rule("DP-1,preferred,auto,1,cm,hdr,sdrbrightness,1.2,sdrsaturation,0.98")
+                .unwrap();
+        assert_eq!(rule.color_mode, Some(ColorMode::Hdr));
+        assert_eq!(rule.sdr_brightness, Some(1.2));
+        assert_eq!(rule.sdr_saturation, Some(0.98));
+        assert!(parse_monitor_rule("DP-1,preferred,auto,1,cm,rec2020").is_err());
+        assert!(parse_monitor_rule("DP-1,preferred,auto,1,sdrbrightness,5").is_err());
+
+        let rule = parse_monitor_rule("DP-1, addreserved, 30, 0, 0, 10").unwrap();
+        assert!(rule.addreserved && !rule.disabled);
+        assert_eq!(
+            rule.reserved,
+            Some(Reserved {
+                top: 30,
+                bottom: 0,
+                left: 0,
+                right: 10
+            })
+        );
+        assert!(parse_monitor_rule("DP-1,addreserved,30,0").is_err());
+
+        assert!(parse_monitor_rule("HDMI-A-1,disable").unwrap().disabled);
+        assert_eq!(
+            parse_monitor_rule(",highrr,auto,1").unwrap().output,
+            OutputSelector::Any
+        );
+        assert!(parse_monitor_rule("DP-1,2560x1440").is_err());
+        assert!(parse_monitor_rule("DP-1,preferred,auto,1,transform,9").is_err());
+    }
 }
@@ -1,16 +1,23 @@
 //! IPC communication with Hyprland.
 
-use crate::hyprland::monitor::{Mode, Monitor, Position, Resolution, Transform};
+use crate::hyprland::backend::MonitorBackend;
+use crate::hyprland::config::{monitor_rule, reserved_rule};
+use crate::hyprland::monitor::{
+    unique_resolutions, ColorMode, Mode, Monitor, Position, Resolution, Transform,
+};
 use anyhow::{Context, Result};
-use std::io::{Read, Write};
+use serde::Deserialize;
+use std::io::{BufRead, BufReader, Read, Write};
 use std::os::unix::net::UnixStream;
+use std::path::PathBuf;
 use std::str::FromStr;
-use tracing::debug;
+use std::sync::mpsc;
+use tracing::{debug, warn};
 
-/// Resolve Hyprland command socket path (matches Hyprland: XDG_RUNTIME_DIR first, then /tmp).
-fn command_socket_path() -> Option<std::path::PathBuf> {
+/// Resolve a socket in the Hyprland instance directory (matches Hyprland: XDG_RUNTIME_DIR first, then /tmp).
+fn instance_socket_path(file: &str) -> Option<std::path::PathBuf> {
     let sig = std::env::var("HYPRLAND_INSTANCE_SIGNATURE").ok()?;
-    let sock = std::path::Path::new("hypr").join(&sig).join(".socket.sock");
+    let sock = std::path::Path::new("hypr").join(&sig).join(file);
     if let Ok(runtime) = std::env::var("XDG_RUNTIME_DIR") {
         let p = std::path::Path::new(&runtime).join(&sock);
         if p.exists() {
@@ -25,6 +32,33 @@ fn command_socket_path() -> Option<std::path::PathBuf> {
     }
 }
 
+/// Resolve Hyprland command socket path.
+fn command_socket_path() -> Option<std::path::PathBuf> {
+    instance_socket_path(".socket.sock")
+}
+
+/// Monitor hotplug notification from Hyprland's event socket.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum MonitorEvent {
+    /// A monitor was connected (connector name)
+    Added(String),
+    /// A monitor was disconnected (connector name)
+    Removed(String),
+}
+
+/// Parse one `EVENT>>DATA` line from socket2; returns None for non-monitor events.
+pub fn parse_monitor_event(line: &str) -> Option<MonitorEvent> {
+    let (event, data) = line.trim_end().split_once(">>")?;
+    match event {
+        "monitoradded" => Some(MonitorEvent::Added(data.to_string())),
+        "monitorremoved" => Some(MonitorEvent::Removed(data.to_string())),
+        // v2 payload is ID,NAME,DESCRIPTION
+        "monitoraddedv2" => Some(MonitorEvent::Added(data.split(',').nth(1)?.to_string())),
+        "monitorremovedv2" => Some(MonitorEvent::Removed(data.split(',').nth(1)?.to_string())),
+        _ => None,
+    }
+}
+
 /// Hyprland transform to our Transform.
 fn hypr_transform_to_ours(t: hyprland::data::Transforms) -> Transform {
     use hyprland::data::Transforms;
@@ -40,8 +74,67 @@ fn hypr_transform_to_ours(t: hyprland::data::Transforms) -> Transform {
     }
 }
 
+/// Fields of `j/monitors` that hyprland-rs does not deserialize.
+#[derive(Debug, Default, Deserialize)]
+struct MonitorExtras {
+    /// Modes like "2560x1440@143.97Hz"
+    #[serde(rename = "availableModes", default)]
+    available_modes: Vec<String>,
+    /// Set for outputs turned off with `NAME,disable` (listed by `monitors all`)
+    #[serde(default)]
+    disabled: bool,
+    /// Mirrored output name, or "none"
+    #[serde(rename = "mirrorOf", default)]
+    mirror_of: Option<String>,
+    /// DRM format, e.g. "XRGB8888" or "XRGB2101010" (10 bit)
+    #[serde(rename = "currentFormat", default)]
+    current_format: String,
+    /// Colour management preset (newer Hyprland only)
+    #[serde(rename = "colorManagementPreset", default)]
+    color_management_preset: Option<String>,
+    #[serde(rename = "sdrBrightness", default)]
+    sdr_brightness: Option<f32>,
+    #[serde(rename = "sdrSaturation", default)]
+    sdr_saturation: Option<f32>,
+}
+
+/// A monitor rule Hyprland did not accept.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct RejectedRule {
+    /// The rule as sent (without `keyword monitor`)
+    pub rule: String,
+    /// Hyprland's reply for that rule
+    pub message: String,
+}
+
+/// Error from applying monitor rules.
+#[derive(Debug, thiserror::Error)]
+pub enum ApplyError {
+    /// Hyprland could not be reached or the request failed as a whole
+    #[error(transparent)]
+    Ipc(#[from] anyhow::Error),
+    /// The batch went through but some rules were rejected (the others are live)
+    #[error("Hyprland rejected {} of {total} monitor rules: {}", .rejected.len(), describe_rejected(.rejected))]
+    Partial {
+        rejected: Vec<RejectedRule>,
+        total: usize,
+    },
+}
+
+/// Short "rule (reason); ..." summary for the status line.
+fn describe_rejected(rejected: &[RejectedRule]) -> String {
+    rejected
+        .iter()
+        .map(|r| format!("{} ({})", r.rule, r.message))
+        .collect::<Vec<_>>()
+        .join("; ")
+}
+
 /// Hyprland IPC client wrapper.
-pub struct HyprlandClient;
+pub struct HyprlandClient {
+    /// Directory holding `.socket.sock`/`.socket2.sock`; None resolves it from the environment
+    instance_dir: Option<PathBuf>,
+}
 
 impl Default for HyprlandClient {
     fn default() -> Self {
@@ -50,44 +143,123 @@ impl Default for HyprlandClient {
 }
 
 impl HyprlandClient {
-    /// Create new client. Does not require async connection; hyprland-rs uses sync socket.
+    /// Create new client for the instance in `HYPRLAND_INSTANCE_SIGNATURE`.
     pub fn new() -> Self {
-        Self
+        Self { instance_dir: None }
+    }
+
+    /// Create a client for the sockets in an explicit instance directory (e.g. a fake server).
+    pub fn with_instance_dir(dir: impl Into<PathBuf>) -> Self {
+        Self {
+            instance_dir: Some(dir.into()),
+        }
+    }
+
+    fn socket_path(&self, file: &str) -> Option<PathBuf> {
+        match &self.instance_dir {
+            Some(dir) => Some(dir.join(file)).filter(|p| p.exists()),
+            None => instance_socket_path(file),
+        }
+    }
+
+    fn command_socket(&self) -> Option<PathBuf> {
+        self.socket_path(".socket.sock")
+    }
+
+    fn event_socket(&self) -> Option<PathBuf> {
+        self.socket_path(".socket2.sock")
+    }
+
+    /// Apply monitor configuration via keyword (uses our socket path).
+    /// Disabled monitors are sent as `NAME,disable`.
+    pub fn apply_monitor_config(&self, monitor: &Monitor) -> Result<()> {
+        let path = self.command_socket().context("Hyprland socket not found")?;
+        for spec in std::iter::once(monitor_rule(monitor)).chain(reserved_rule(monitor)) {
+            debug!("Setting monitor keyword: {}", spec);
+            let _ = raw_socket_request(path.clone(), &format!("/keyword monitor {}", spec))
+                .context("Failed to apply monitor configuration")?;
+        }
+        Ok(())
     }
 
-    /// Get all monitors with their current configuration.
+    /// Check if Hyprland is likely running (env set and socket exists).
+    /// Checks both XDG_RUNTIME_DIR and /tmp to match Hyprland’s socket location.
+    pub fn is_available() -> bool {
+        command_socket_path().is_some()
+    }
+}
+
+impl MonitorBackend for HyprlandClient {
+    /// Get all monitors (including disabled ones) with their current configuration.
     /// Uses our own socket path (XDG_RUNTIME_DIR first) so it works when Hyprland
     /// uses XDG_RUNTIME_DIR and hyprland-rs would look only in /tmp.
-    pub fn get_monitors(&self) -> Result<Vec<Monitor>> {
-        let path = command_socket_path()
+    fn get_monitors(&self) -> Result<Vec<Monitor>> {
+        let path = self
+            .command_socket()
             .context("Hyprland socket not found (is Hyprland running?)")?;
-        let response = raw_socket_request(path, "j/monitors")?;
-        let hypr_monitors: Vec<hyprland::data::Monitor> =
+        let response = raw_socket_request(path, "j/monitors all")?;
+        let raw: Vec<serde_json::Value> =
             serde_json::from_str(&response).context("Failed to parse monitors JSON")?;
         let mut monitors = Vec::new();
-        for m in &hypr_monitors {
+        for value in raw {
+            let m: hyprland::data::Monitor =
+                serde_json::from_value(value.clone()).context("Failed to parse monitor JSON")?;
+            let extras: MonitorExtras = serde_json::from_value(value).unwrap_or_default();
             let resolution = Resolution {
                 width: u32::from(m.width),
                 height: u32::from(m.height),
             };
             let position = Position { x: m.x, y: m.y };
             let transform = hypr_transform_to_ours(m.transform.clone());
-            let res_list = vec![resolution.clone()];
-            let hz_list = vec![m.refresh_rate];
+            let mut modes: Vec<Mode> = extras
+                .available_modes
+                .iter()
+                .filter_map(|s| parse_mode(s))
+                .collect();
+            if modes.is_empty() {
+                modes.push(Mode {
+                    width: resolution.width,
+                    height: resolution.height,
+                    refresh_rate: m.refresh_rate,
+                });
+            }
 
-            monitors.push(Monitor {
+            let mut monitor = Monitor {
                 name: m.name.clone(),
                 description: m.description.clone(),
                 position,
                 resolution: resolution.clone(),
-                available_resolutions: res_list,
+                available_resolutions: unique_resolutions(&modes),
                 refresh_rate: m.refresh_rate,
-                available_refresh_rates: hz_list,
+                available_refresh_rates: Vec::new(),
+                available_modes: modes,
                 scale: m.scale,
                 transform,
-                enabled: m.dpms_status,
+                enabled: !extras.disabled,
                 primary: m.focused,
-            });
+                mode_spec: None,
+                position_spec: None,
+                mirror_of: extras.mirror_of.filter(|m| !m.is_empty() && m != "none"),
+                // `vrr` in j/monitors says whether VRR is active right now, not the setting.
+                vrr: None,
+                bitdepth: if extras.current_format.contains("2101010") {
+                    10
+                } else {
+                    8
+                },
+                // Defaults are left unset so rules only carry what the user configured.
+                color_mode: extras
+                    .color_management_preset
+                    .as_deref()
+                    .and_then(ColorMode::from_hyprland_str)
+                    .filter(|cm| *cm != ColorMode::Srgb),
+                sdr_brightness: extras.sdr_brightness.filter(|v| *v != 1.0),
+                sdr_saturation: extras.sdr_saturation.filter(|v| *v != 1.0),
+                // `reserved` in j/monitors includes bar exclusive zones, not just rules.
+                reserved: None,
+            };
+            monitor.available_refresh_rates = monitor.refresh_rates_for(&resolution);
+            monitors.push(monitor);
         }
         if !monitors.is_empty() && !monitors.iter().any(|m| m.primary) {
             monitors[0].primary = true;
@@ -95,50 +267,102 @@ impl HyprlandClient {
         Ok(monitors)
     }
 
-    /// Get available modes for a specific monitor (async placeholder for future API).
-    pub async fn get_available_modes(&self, monitor_name: &str) -> Result<Vec<Mode>> {
-        let _ = monitor_name;
-        Ok(Vec::new())
-    }
-
-    /// Apply monitor configuration via keyword (uses our socket path).
-    pub fn apply_monitor_config(&self, monitor: &Monitor) -> Result<()> {
-        if !monitor.enabled {
+    /// Apply all monitor rules atomically in one `[[BATCH]]` request, so Hyprland
+    /// re-lays out the screen once. Reports each rule Hyprland rejected.
+    fn apply_all(&self, monitors: &[Monitor]) -> std::result::Result<(), ApplyError> {
+        if monitors.is_empty() {
             return Ok(());
         }
-        let path = command_socket_path()
-            .context("Hyprland socket not found")?;
-        let spec = format!(
-            "{},{}@{:.2},{}x{},{}",
-            monitor.name,
-            monitor.resolution,
-            monitor.refresh_rate,
-            monitor.position.x,
-            monitor.position.y,
-            monitor.scale
-        );
-        debug!("Setting monitor keyword: {}", spec);
-        let _ = raw_socket_request(path, &format!("/keyword monitor {}", spec))
-            .context("Failed to apply monitor configuration")?;
-        Ok(())
-    }
-
-    /// Apply all monitor configurations (set keyword for each; last may win in some setups).
-    pub fn apply_all(&self, monitors: &[Monitor]) -> Result<()> {
-        let enabled: Vec<_> = monitors.iter().filter(|m| m.enabled).collect();
-        for m in &enabled {
-            self.apply_monitor_config(m)?;
+        let path = self.command_socket().context("Hyprland socket not found")?;
+        let rules: Vec<String> = monitors
+            .iter()
+            .flat_map(|m| std::iter::once(monitor_rule(m)).chain(reserved_rule(m)))
+            .collect();
+        let request = batch_request(&rules);
+        debug!("Sending monitor batch: {}", request);
+        let response =
+            raw_socket_request(path, &request).context("Failed to apply monitor configuration")?;
+        let rejected = parse_batch_response(&rules, &response);
+        if rejected.is_empty() {
+            Ok(())
+        } else {
+            Err(ApplyError::Partial {
+                rejected,
+                total: rules.len(),
+            })
         }
-        Ok(())
     }
 
-    /// Check if Hyprland is likely running (env set and socket exists).
-    /// Checks both XDG_RUNTIME_DIR and /tmp to match Hyprland’s socket location.
-    pub fn is_available() -> bool {
-        command_socket_path().is_some()
+    /// Subscribe to monitor hotplug events on socket2.
+    /// Spawns a reader thread; the channel closes when Hyprland closes the socket.
+    fn subscribe_events(&self) -> Result<mpsc::Receiver<MonitorEvent>> {
+        let path = self
+            .event_socket()
+            .context("Hyprland event socket not found")?;
+        let stream =
+            UnixStream::connect(&path).context("Failed to connect to Hyprland event socket")?;
+        let (tx, rx) = mpsc::channel();
+        std::thread::spawn(move || {
+            for line in BufReader::new(stream).lines() {
+                let line = match line {
+                    Ok(l) => l,
+                    Err(e) => {
+                        warn!("Hyprland event socket read failed: {}", e);
+                        break;
+                    }
+                };
+                if let Some(ev) = parse_monitor_event(&line) {
+                    debug!("Monitor event: {:?}", ev);
+                    if tx.send(ev).is_err() {
+                        break;
+                    }
+                }
+            }
+        });
+        Ok(rx)
     }
 }
 
+/// Build a `[[BATCH]]` request setting every rule with `keyword monitor`.
+fn batch_request(rules: &[String]) -> String {
+    let commands: Vec<String> = rules
+        .iter()
+        .map(|r| format!("/keyword monitor {}", r))
+        .collect();
+    format!("[[BATCH]]{}", commands.join(";"))
+}
+
+/// Hyprland's `dispatchBatch` (HyprCtl.cpp) appends this after every reply but the last.
+/// See tests/fixtures/hyprctl_batch_reply.txt.
+const BATCH_DELIMITER: &str = "\n\n\n";
+
+/// Match batch replies to rules; every reply other than "ok" is a rejection.
+///
+/// `keyword` answers with an error only when Hyprland cannot parse the rule. A rule it
+/// parses but cannot use (a mode the output lacks, a scale that does not divide the
+/// resolution) still answers "ok"; Hyprland falls back and reports it in its log and
+/// config error bar. So an empty result does not prove the layout is exactly as sent.
+fn parse_batch_response(rules: &[String], response: &str) -> Vec<RejectedRule> {
+    let mut replies = response.split(BATCH_DELIMITER);
+    rules
+        .iter()
+        .filter_map(|rule| {
+            let reply = replies.next().map(str::trim);
+            match reply {
+                Some("ok") => None,
+                Some(msg) if !msg.is_empty() => Some(RejectedRule {
+                    rule: rule.clone(),
+                    message: msg.to_string(),
+                }),
+                _ => Some(RejectedRule {
+                    rule: rule.clone(),
+                    message: "no reply".to_string(),
+                }),
+            }
+        })
+        .collect()
+}
+
 /// Send a raw command to the Hyprland socket and return the response.
 fn raw_socket_request(path: std::path::PathBuf, command: &str) -> Result<String> {
     let mut stream = UnixStream::connect(&path).context("Failed to connect to Hyprland socket")?;
@@ -160,8 +384,18 @@ fn raw_socket_request(path: std::path::PathBuf, command: &str) -> Result<String>
     String::from_utf8(response).context("Invalid UTF-8 from socket")
 }
 
-/// Parse resolution@refresh string (for tests / fallback).
-#[allow(dead_code)]
+/// Parse an `availableModes` entry like "2560x1440@143.97Hz".
+fn parse_mode(s: &str) -> Option<Mode> {
+    let s = s.trim().trim_end_matches("Hz");
+    let (res, refresh_rate) = parse_resolution_refresh(s).ok()?;
+    Some(Mode {
+        width: res.width,
+        height: res.height,
+        refresh_rate,
+    })
+}
+
+/// Parse resolution@refresh string.
 fn parse_resolution_refresh(s: &str) -> Result<(Resolution, f32)> {
     let s = s.trim();
     let (res_part, hz_part) = s
@@ -207,6 +441,84 @@ mod tests {
         assert!((hz - 144.0).abs() < 0.01);
     }
 
+    #[test]
+    fn test_parse_mode() {
+        let mode = parse_mode("2560x1440@143.97Hz").unwrap();
+        assert_eq!((mode.width, mode.height), (2560, 1440));
+        assert!((mode.refresh_rate - 143.97).abs() < 0.01);
+        assert!(parse_mode("garbage").is_none());
+    }
+
+    #[test]
+    fn test_parse_monitor_event() {
+        assert_eq!(
+            parse_monitor_event("monitoradded>>DP-1\n"),
+            Some(MonitorEvent::Added("DP-1".to_string()))
+        );
+        assert_eq!(
+            parse_monitor_event("monitoraddedv2>>2,HDMI-A-1,Dell Inc. U2720Q"),
+            Some(MonitorEvent::Added("HDMI-A-1".to_string()))
+        );
+        assert_eq!(
+            parse_monitor_event("monitorremoved>>DP-1"),
+            Some(MonitorEvent::Removed("DP-1".to_string()))
+        );
+        assert_eq!(parse_monitor_event("workspace>>2"), None);
+    }
+
+    #[test]
+    fn test_batch_request() {
+        let rules = vec![
+            "DP-1,2560x1440@144.00,0x0,1".to_string(),
+            "eDP-1,disable".to_string(),
+        ];
+        assert_eq!(
+            batch_request(&rules),
+            "[[BATCH]]/keyword monitor DP-1,2560x1440@144.00,0x0,1;/keyword monitor eDP-1,disable"
+        );
+    }
+
+    /// Reply to a three-command `[[BATCH]]`, framed like Hyprland's `dispatchBatch`
+    /// (reconstructed from HyprCtl.cpp, not captured from a running compositor).
+    const BATCH_REPLY: &str = include_str!("../../tests/fixtures/hyprctl_batch_reply.txt");
+
+    #[test]
+    fn test_parse_batch_response_reads_hyprland_reply() {
+        let rules = vec![
+            "DP-1,2560x1440@144.00,0x0,1".to_string(),
+            "DP-1,addreserved,30,0,0,0".to_string(),
+            "eDP-1,disable".to_string(),
+        ];
+        assert!(!BATCH_REPLY.ends_with(BATCH_DELIMITER));
+        assert_eq!(BATCH_REPLY.split(BATCH_DELIMITER).count(), rules.len());
+        assert!(parse_batch_response(&rules, BATCH_REPLY).is_empty());
+        // A shorter reply leaves the trailing rules unanswered.
+        assert_eq!(parse_batch_response(&rules, "ok").len(), 2);
+    }
+
+    #[test]
+    fn test_parse_batch_response_reports_rejected_rule() {
+        let rules = vec![
+            "DP-1,a".to_string(),
+            "DP-2,b".to_string(),
+            "DP-3,c".to_string(),
+        ];
+        assert!(parse_batch_response(&rules, "ok\n\n\nok\n\n\nok").is_empty());
+
+        let rejected = parse_batch_response(&rules, "ok\n\n\ninvalid resolution\n\n\nok");
+        assert_eq!(
+            rejected,
+            vec![RejectedRule {
+                rule: "DP-2,b".to_string(),
+                message: "invalid resolution".to_string(),
+            }]
+        );
+
+        let missing = parse_batch_response(&rules, "ok");
+        assert_eq!(missing.len(), 2);
+        assert_eq!(missing[0].message, "no reply");
+    }
+
     #[test]
     fn test_parse_position() {
         let (x, y) = parse_position("2560_0").unwrap();
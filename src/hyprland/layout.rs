@@ -0,0 +1,349 @@
+//! Layout geometry (edge snapping between monitors) in logical pixels.
+
+use crate::hyprland::monitor::{Monitor, Position, PositionSpec};
+
+/// Bounding box of a monitor in layout pixels: (x, y, width, height).
+fn bounds(m: &Monitor) -> (i32, i32, i32, i32) {
+    let (w, h) = m.logical_size();
+    (m.position.x, m.position.y, w as i32, h as i32)
+}
+
+/// Whether two monitors cover a common area of the layout (touching edges don't count).
+pub fn overlaps(a: &Monitor, b: &Monitor) -> bool {
+    let (ax, ay, aw, ah) = bounds(a);
+    let (bx, by, bw, bh) = bounds(b);
+    ax < bx + bw && bx < ax + aw && ay < by + bh && by < ay + ah
+}
+
+/// Resolve automatic positions in order, as Hyprland does: each auto-placed monitor goes
+/// next to the bounding box of the enabled monitors placed before it (fixed ones first).
+pub fn resolve_auto_positions(monitors: &mut [Monitor]) {
+    let is_auto = |m: &Monitor| m.position_spec.as_ref().is_some_and(|s| s.is_symbolic());
+    let mut placed: Vec<(i32, i32, i32, i32)> = monitors
+        .iter()
+        .filter(|m| m.enabled && m.mirror_of.is_none() && !is_auto(m))
+        .map(bounds)
+        .collect();
+    for m in monitors.iter_mut().filter(|m| is_auto(m)) {
+        let (_, _, w, h) = bounds(m);
+        let extent = placed.iter().fold(
+            None,
+            |acc: Option<(i32, i32, i32, i32)>, &(x, y, bw, bh)| {
+                Some(match acc {
+                    None => (x, y, x + bw, y + bh),
+                    Some((x1, y1, x2, y2)) => {
+                        (x1.min(x), y1.min(y), x2.max(x + bw), y2.max(y + bh))
+                    }
+                })
+            },
+        );
+        let (x, y) = match (extent, m.position_spec.as_ref()) {
+            (None, _) => (0, 0),
+            (Some((x1, y1, _, _)), Some(PositionSpec::AutoLeft)) => (x1 - w, y1),
+            (Some((x1, y1, _, _)), Some(PositionSpec::AutoUp)) => (x1, y1 - h),
+            (Some((x1, _, _, y2)), Some(PositionSpec::AutoDown)) => (x1, y2),
+            (Some((_, y1, x2, _)), _) => (x2, y1),
+        };
+        m.position = Position { x, y };
+        if m.enabled && m.mirror_of.is_none() {
+            placed.push(bounds(m));
+        }
+    }
+}
+
+/// Smallest offset (by magnitude) from `candidates` that is strictly below `threshold`.
+fn nearest(candidates: impl Iterator<Item = i32>, threshold: i32) -> Option<i32> {
+    candidates
+        .filter(|d| d.abs() < threshold)
+        .min_by_key(|d| d.abs())
+}
+
+/// Position for `monitors[index]` snapped flush to the nearest edge of another enabled,
+/// non-mirroring monitor, per axis, if that edge is closer than `threshold` pixels.
+pub fn snap_to_neighbours(monitors: &[Monitor], index: usize, threshold: i32) -> Position {
+    let Some(m) = monitors.get(index) else {
+        return Position::default();
+    };
+    let (x, y, w, h) = bounds(m);
+    let others: Vec<_> = monitors
+        .iter()
+        .enumerate()
+        .filter(|(i, o)| *i != index && o.enabled && o.mirror_of.is_none())
+        .map(|(_, o)| bounds(o))
+        .collect();
+    // Candidate offsets: edge-to-edge adjacency and edge alignment.
+    let dx = nearest(
+        others
+            .iter()
+            .flat_map(|&(ox, _, ow, _)| [ox - (x + w), ox + ow - x, ox - x, ox + ow - (x + w)]),
+        threshold,
+    );
+    let dy = nearest(
+        others
+            .iter()
+            .flat_map(|&(_, oy, _, oh)| [oy - (y + h), oy + oh - y, oy - y, oy + oh - (y + h)]),
+        threshold,
+    );
+    Position {
+        x: x + dx.unwrap_or(0),
+        y: y + dy.unwrap_or(0),
+    }
+}
+
+/// How serious a layout issue is; errors block applying the layout.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Severity {
+    Warning,
+    Error,
+}
+
+/// Kind of problem found by [`validate_layout`].
+#[derive(Clone, Debug, PartialEq)]
+pub enum LayoutIssueKind {
+    /// Two monitors cover the same area
+    Overlap,
+    /// Two facing monitors are this many pixels apart, so the cursor can't cross
+    Gap(i32),
+    /// A group of monitors touches none of the others
+    Island,
+    /// A monitor starts left of or above the origin
+    NegativePosition,
+    /// No monitor is enabled
+    AllDisabled,
+}
+
+/// One problem with a layout and the monitors involved.
+#[derive(Clone, Debug, PartialEq)]
+pub struct LayoutIssue {
+    pub severity: Severity,
+    pub kind: LayoutIssueKind,
+    /// Connector names
+    pub monitors: Vec<String>,
+}
+
+impl std::fmt::Display for LayoutIssue {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let names = self.monitors.join(", ");
+        match &self.kind {
+            LayoutIssueKind::Overlap => write!(f, "{} overlap", names),
+            LayoutIssueKind::Gap(d) => write!(f, "{}px gap between {}", d, names),
+            LayoutIssueKind::Island => write!(f, "{} not connected to the other monitors", names),
+            LayoutIssueKind::NegativePosition => write!(f, "{} has a negative position", names),
+            LayoutIssueKind::AllDisabled => write!(f, "All monitors are disabled"),
+        }
+    }
+}
+
+/// Length of the overlap of [a1, a2) and [b1, b2).
+fn span_overlap(a1: i32, a2: i32, b1: i32, b2: i32) -> i32 {
+    a2.min(b2) - a1.max(b1)
+}
+
+/// Distance between two boxes facing each other along one axis (0 if they touch),
+/// or None if they don't face each other or overlap.
+fn facing_distance(a: (i32, i32, i32, i32), b: (i32, i32, i32, i32)) -> Option<i32> {
+    let (ax, ay, aw, ah) = a;
+    let (bx, by, bw, bh) = b;
+    if span_overlap(ay, ay + ah, by, by + bh) > 0 {
+        let d = (bx - (ax + aw)).max(ax - (bx + bw));
+        return (d >= 0).then_some(d);
+    }
+    if span_overlap(ax, ax + aw, bx, bx + bw) > 0 {
+        let d = (by - (ay + ah)).max(ay - (by + bh));
+        return (d >= 0).then_some(d);
+    }
+    None
+}
+
+/// Group representative of `i` (union-find over touching monitors).
+fn root(group: &mut [usize], i: usize) -> usize {
+    let mut r = i;
+    while group[r] != r {
+        r = group[r];
+    }
+    group[i] = r;
+    r
+}
+
+/// Check the enabled, non-mirroring monitors for overlaps, gaps, disconnected islands and
+/// negative positions. Sorted with errors first.
+pub fn validate_layout(monitors: &[Monitor]) -> Vec<LayoutIssue> {
+    let issue = |severity, kind, monitors: Vec<&Monitor>| LayoutIssue {
+        severity,
+        kind,
+        monitors: monitors.iter().map(|m| m.name.clone()).collect(),
+    };
+    if !monitors.is_empty() && monitors.iter().all(|m| !m.enabled) {
+        return vec![issue(Severity::Error, LayoutIssueKind::AllDisabled, vec![])];
+    }
+    let active: Vec<&Monitor> = monitors
+        .iter()
+        .filter(|m| m.enabled && m.mirror_of.is_none())
+        .collect();
+    let mut issues = Vec::new();
+    for m in active
+        .iter()
+        .filter(|m| m.position.x < 0 || m.position.y < 0)
+    {
+        issues.push(issue(
+            Severity::Warning,
+            LayoutIssueKind::NegativePosition,
+            vec![m],
+        ));
+    }
+
+    // Group monitors that share an edge; the cursor can only move within a group.
+    let mut group: Vec<usize> = (0..active.len()).collect();
+    let mut gaps = Vec::new();
+    for i in 0..active.len() {
+        for j in i + 1..active.len() {
+            let (a, b) = (active[i], active[j]);
+            let overlap = overlaps(a, b);
+            if overlap {
+                issues.push(issue(Severity::Error, LayoutIssueKind::Overlap, vec![a, b]));
+            }
+            match facing_distance(bounds(a), bounds(b)) {
+                _ if overlap => {}
+                Some(d) if d > 0 => {
+                    gaps.push((i, j, d));
+                    continue;
+                }
+                Some(_) => {}
+                None => continue,
+            }
+            // Touching or overlapping: the cursor can cross.
+            let (ri, rj) = (root(&mut group, i), root(&mut group, j));
+            group[ri] = rj;
+        }
+    }
+    for (i, j, d) in gaps {
+        if root(&mut group, i) != root(&mut group, j) {
+            let kind = LayoutIssueKind::Gap(d);
+            issues.push(issue(Severity::Warning, kind, vec![active[i], active[j]]));
+        }
+    }
+
+    // Every group except the one holding the first monitor is an island.
+    let main = (!active.is_empty()).then(|| root(&mut group, 0));
+    let mut islands: Vec<(usize, Vec<&Monitor>)> = Vec::new();
+    for (i, m) in active.iter().enumerate() {
+        let r = root(&mut group, i);
+        if Some(r) == main {
+            continue;
+        }
+        match islands.iter_mut().find(|(g, _)| *g == r) {
+            Some((_, members)) => members.push(m),
+            None => islands.push((r, vec![m])),
+        }
+    }
+    for (_, members) in islands {
+        issues.push(issue(Severity::Warning, LayoutIssueKind::Island, members));
+    }
+    issues.sort_by_key(|i| std::cmp::Reverse(i.severity));
+    issues
+}
+
+/// Automatic arrangement of the enabled monitors (see [`arrange`]).
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Arrangement {
+    /// Side by side, left to right, top edges aligned
+    RowTop,
+    /// Side by side, left to right, vertically centred
+    RowCentered,
+    /// Stacked top to bottom, horizontally centred
+    Column,
+    /// External monitors in a row with the laptop panel centred below
+    LShape,
+}
+
+impl std::fmt::Display for Arrangement {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let s = match self {
+            Arrangement::RowTop => "row (top aligned)",
+            Arrangement::RowCentered => "row (centred)",
+            Arrangement::Column => "column",
+            Arrangement::LShape => "L-shape",
+        };
+        write!(f, "{}", s)
+    }
+}
+
+/// Whether the connector is a built-in panel.
+fn is_laptop_panel(m: &Monitor) -> bool {
+    ["eDP", "LVDS", "DSI"].iter().any(|p| m.name.starts_with(p))
+}
+
+/// Place `indices` side by side from (0, 0) in the given order; returns the row size.
+fn place_row(monitors: &mut [Monitor], indices: &[usize], centred: bool) -> (i32, i32) {
+    let height = indices
+        .iter()
+        .map(|&i| bounds(&monitors[i]).3)
+        .max()
+        .unwrap_or(0);
+    let mut x = 0;
+    for &i in indices {
+        let (_, _, w, h) = bounds(&monitors[i]);
+        let y = if centred { (height - h) / 2 } else { 0 };
+        monitors[i].position = Position { x, y };
+        x += w;
+    }
+    (x, height)
+}
+
+/// Rearrange the enabled, non-mirroring monitors flush against each other using their
+/// logical sizes, keeping their current left-to-right (or top-to-bottom) order.
+/// Disabled and mirroring monitors keep their positions.
+pub fn arrange(monitors: &mut [Monitor], arrangement: Arrangement) -> Result<(), String> {
+    let mut indices: Vec<usize> = (0..monitors.len())
+        .filter(|&i| monitors[i].enabled && monitors[i].mirror_of.is_none())
+        .collect();
+    if indices.is_empty() {
+        return Err("No enabled monitor to arrange".to_string());
+    }
+    let key = |m: &Monitor| (m.position.x, m.position.y);
+    match arrangement {
+        Arrangement::RowTop | Arrangement::RowCentered => {
+            indices.sort_by_key(|&i| key(&monitors[i]));
+            place_row(monitors, &indices, arrangement == Arrangement::RowCentered);
+        }
+        Arrangement::Column => {
+            indices.sort_by_key(|&i| (monitors[i].position.y, monitors[i].position.x));
+            let width = indices
+                .iter()
+                .map(|&i| bounds(&monitors[i]).2)
+                .max()
+                .unwrap_or(0);
+            let mut y = 0;
+            for &i in &indices {
+                let (_, _, w, h) = bounds(&monitors[i]);
+                monitors[i].position = Position {
+                    x: (width - w) / 2,
+                    y,
+                };
+                y += h;
+            }
+        }
+        Arrangement::LShape => {
+            let laptop = indices
+                .iter()
+                .position(|&i| is_laptop_panel(&monitors[i]))
+                .map(|p| indices.remove(p))
+                .ok_or("No laptop panel (eDP) to place below the other monitors")?;
+            if indices.is_empty() {
+                return Err("No external monitor to place above the laptop panel".to_string());
+            }
+            indices.sort_by_key(|&i| key(&monitors[i]));
+            let (row_w, row_h) = place_row(monitors, &indices, false);
+            let (_, _, w, _) = bounds(&monitors[laptop]);
+            monitors[laptop].position = Position {
+                x: (row_w - w) / 2,
+                y: row_h,
+            };
+            indices.push(laptop);
+        }
+    }
+    for i in indices {
+        monitors[i].position_spec = None;
+    }
+    Ok(())
+}
@@ -1,9 +1,29 @@
 //! Hyprland integration (IPC, monitor data, config generation).
 
+mod backend;
 mod config;
 mod ipc;
+mod layout;
 mod monitor;
+mod persist;
 
-pub use config::{generate_config, generate_config_with_workspaces};
-pub use ipc::HyprlandClient;
-pub use monitor::{Mode, Monitor, Position, Resolution, Transform};
+pub use backend::MonitorBackend;
+pub use config::{
+    generate_config, generate_config_with, generate_config_with_workspaces, monitor_rule,
+    parse_monitor_rule, parse_monitorv2_block, read_monitor_rules, reserved_rule, ConfigSyntax,
+    ExportOptions, MonitorRule, OutputSelector,
+};
+pub use ipc::{parse_monitor_event, ApplyError, HyprlandClient, MonitorEvent, RejectedRule};
+pub use layout::{
+    arrange, overlaps, resolve_auto_positions, snap_to_neighbours, validate_layout, Arrangement,
+    LayoutIssue, LayoutIssueKind, Severity,
+};
+pub use monitor::{
+    is_valid_scale, mirror_cycle, parse_sdr_value, unique_resolutions, valid_scales,
+    valid_scales_near, ColorMode, Mode, ModeSpec, Monitor, Position, PositionSpec, Reserved,
+    Resolution, Transform, Vrr, SDR_BRIGHTNESS_RANGE, SDR_SATURATION_RANGE,
+};
+pub use persist::{
+    ensure_source_line, expand_home, persist, replace_managed_block, write_atomic, PersistMode,
+    PersistReport, PersistTarget, BEGIN_MARKER, END_MARKER,
+};
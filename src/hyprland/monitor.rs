@@ -19,6 +19,9 @@ pub struct Monitor {
     pub refresh_rate: f32,
     /// Available refresh rates
     pub available_refresh_rates: Vec<f32>,
+    /// All modes the monitor advertises (resolution + refresh rate)
+    #[serde(default)]
+    pub available_modes: Vec<Mode>,
     /// Display scale factor
     pub scale: f32,
     /// Display transform (rotation/flip)
@@ -27,6 +30,124 @@ pub struct Monitor {
     pub enabled: bool,
     /// Whether this is the primary monitor
     pub primary: bool,
+    /// Mode picked by Hyprland (`preferred`, `highres`, ... or `WxH` without a refresh rate);
+    /// `resolution` and `refresh_rate` hold it resolved
+    #[serde(default)]
+    pub mode_spec: Option<ModeSpec>,
+    /// Automatic placement (`auto`, `auto-right`, ...); `position` holds it resolved
+    #[serde(default)]
+    pub position_spec: Option<PositionSpec>,
+    /// Output whose picture this monitor mirrors
+    #[serde(default)]
+    pub mirror_of: Option<String>,
+    /// Variable refresh rate; None leaves it to Hyprland's `misc:vrr`
+    #[serde(default)]
+    pub vrr: Option<Vrr>,
+    /// Colour depth in bits per channel (8 or 10)
+    #[serde(default = "default_bitdepth")]
+    pub bitdepth: u8,
+    /// Colour management preset (`cm`); None is Hyprland's default (sRGB)
+    #[serde(default)]
+    pub color_mode: Option<ColorMode>,
+    /// SDR content brightness in HDR mode (`sdrbrightness`)
+    #[serde(default)]
+    pub sdr_brightness: Option<f32>,
+    /// SDR content saturation in HDR mode (`sdrsaturation`)
+    #[serde(default)]
+    pub sdr_saturation: Option<f32>,
+    /// Area kept free of windows (`addreserved`), e.g. for a bar; an empty inset
+    /// resets one applied earlier
+    #[serde(default)]
+    pub reserved: Option<Reserved>,
+}
+
+fn default_bitdepth() -> u8 {
+    8
+}
+
+impl Monitor {
+    /// Refresh rates supported at the given resolution, highest first.
+    pub fn refresh_rates_for(&self, resolution: &Resolution) -> Vec<f32> {
+        let mut rates: Vec<f32> = self
+            .available_modes
+            .iter()
+            .filter(|m| m.width == resolution.width && m.height == resolution.height)
+            .map(|m| m.refresh_rate)
+            .collect();
+        rates.sort_by(|a, b| b.total_cmp(a));
+        rates.dedup_by(|a, b| (*a - *b).abs() < 0.01);
+        rates
+    }
+
+    /// Size in layout (logical) pixels, as Hyprland places outputs: the resolution
+    /// divided by the scale, with width and height swapped for 90/270 degree transforms.
+    pub fn logical_size(&self) -> (u32, u32) {
+        let scale = if self.scale > 0.0 { self.scale } else { 1.0 };
+        let w = (self.resolution.width as f32 / scale).round() as u32;
+        let h = (self.resolution.height as f32 / scale).round() as u32;
+        if self.transform.is_rotated() {
+            (h, w)
+        } else {
+            (w, h)
+        }
+    }
+}
+
+/// Hyprland scales are multiples of 1/120.
+const SCALE_STEPS: u32 = 120;
+/// Largest scale considered when suggesting alternatives.
+const MAX_SUGGESTED_SCALE: u32 = 8;
+
+/// Whether `steps`/120 gives a whole-pixel logical size for `resolution`.
+fn scale_steps_fit(resolution: &Resolution, steps: u32) -> bool {
+    steps > 0
+        && (resolution.width * SCALE_STEPS).is_multiple_of(steps)
+        && (resolution.height * SCALE_STEPS).is_multiple_of(steps)
+}
+
+/// Whether Hyprland accepts `scale` for `resolution` as is: a multiple of 1/120
+/// that divides the resolution into whole logical pixels.
+pub fn is_valid_scale(resolution: &Resolution, scale: f32) -> bool {
+    let steps = scale * SCALE_STEPS as f32;
+    (steps - steps.round()).abs() < 1e-3 && scale_steps_fit(resolution, steps.round() as u32)
+}
+
+/// Valid scales for `resolution` within `range`, ascending.
+pub fn valid_scales(resolution: &Resolution, range: std::ops::RangeInclusive<f32>) -> Vec<f32> {
+    (1..=MAX_SUGGESTED_SCALE * SCALE_STEPS)
+        .filter(|&k| scale_steps_fit(resolution, k))
+        .map(|k| k as f32 / SCALE_STEPS as f32)
+        .filter(|s| range.contains(s))
+        .collect()
+}
+
+/// The `count` valid scales for `resolution` closest to `scale`, ascending.
+pub fn valid_scales_near(resolution: &Resolution, scale: f32, count: usize) -> Vec<f32> {
+    let mut scales = valid_scales(resolution, 0.0..=MAX_SUGGESTED_SCALE as f32);
+    scales.sort_by(|a, b| (a - scale).abs().total_cmp(&(b - scale).abs()));
+    scales.truncate(count);
+    scales.sort_by(f32::total_cmp);
+    scales
+}
+
+/// Whether making `name` mirror `source` would create a mirror cycle (A mirrors B mirrors A).
+pub fn mirror_cycle(monitors: &[Monitor], name: &str, source: &str) -> bool {
+    let mut current = source;
+    // Each monitor is visited at most once on an acyclic chain.
+    for _ in 0..=monitors.len() {
+        if current == name {
+            return true;
+        }
+        match monitors
+            .iter()
+            .find(|m| m.name == current)
+            .and_then(|m| m.mirror_of.as_deref())
+        {
+            Some(next) => current = next,
+            None => return false,
+        }
+    }
+    true
 }
 
 /// Screen position.
@@ -36,6 +157,36 @@ pub struct Position {
     pub y: i32,
 }
 
+/// Reserved area inset in logical pixels (`addreserved,T,B,L,R`).
+#[derive(Clone, Copy, Debug, Serialize, Deserialize, PartialEq, Eq, Default)]
+pub struct Reserved {
+    #[serde(default)]
+    pub top: u32,
+    #[serde(default)]
+    pub bottom: u32,
+    #[serde(default)]
+    pub left: u32,
+    #[serde(default)]
+    pub right: u32,
+}
+
+impl Reserved {
+    pub fn is_empty(&self) -> bool {
+        *self == Reserved::default()
+    }
+}
+
+impl std::fmt::Display for Reserved {
+    /// Hyprland order: top, bottom, left, right.
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{},{},{},{}",
+            self.top, self.bottom, self.left, self.right
+        )
+    }
+}
+
 /// Screen resolution.
 #[derive(Clone, Debug, Serialize, Deserialize, PartialEq)]
 pub struct Resolution {
@@ -49,6 +200,352 @@ impl std::fmt::Display for Resolution {
     }
 }
 
+/// Mode field of a monitor rule: a fixed mode or one Hyprland picks.
+#[derive(Clone, Debug, PartialEq, Default, Serialize, Deserialize)]
+#[serde(try_from = "String", into = "String")]
+pub enum ModeSpec {
+    /// Monitor's preferred mode
+    #[default]
+    Preferred,
+    /// Highest resolution
+    HighRes,
+    /// Highest refresh rate
+    HighRr,
+    /// Widest resolution
+    MaxWidth,
+    /// Fixed resolution, optionally with a refresh rate
+    Explicit {
+        resolution: Resolution,
+        refresh_rate: Option<f32>,
+    },
+}
+
+impl ModeSpec {
+    /// Parse `preferred`, `highres`, `highrr`, `maxwidth` or `WxH[@HZ]`.
+    pub fn parse(s: &str) -> Option<Self> {
+        match s {
+            "preferred" => Some(ModeSpec::Preferred),
+            "highres" => Some(ModeSpec::HighRes),
+            "highrr" => Some(ModeSpec::HighRr),
+            "maxwidth" => Some(ModeSpec::MaxWidth),
+            _ => {
+                let (res, hz) = match s.split_once('@') {
+                    Some((res, hz)) => (res, Some(hz.trim_end_matches("Hz").parse().ok()?)),
+                    None => (s, None),
+                };
+                let (w, h) = res.split_once('x')?;
+                Some(ModeSpec::Explicit {
+                    resolution: Resolution {
+                        width: w.parse().ok()?,
+                        height: h.parse().ok()?,
+                    },
+                    refresh_rate: hz,
+                })
+            }
+        }
+    }
+
+    /// Whether Hyprland picks the mode (anything but `Explicit`).
+    pub fn is_symbolic(&self) -> bool {
+        !matches!(self, ModeSpec::Explicit { .. })
+    }
+
+    /// Whether Hyprland picks the mode or at least its refresh rate.
+    pub fn leaves_refresh_rate(&self) -> bool {
+        !matches!(
+            self,
+            ModeSpec::Explicit {
+                refresh_rate: Some(_),
+                ..
+            }
+        )
+    }
+
+    /// Mode this spec selects from the advertised modes (preferred is listed first).
+    /// A resolution without a refresh rate gets the highest rate advertised for it.
+    pub fn resolve(&self, modes: &[Mode]) -> Option<(Resolution, f32)> {
+        let area = |m: &Mode| u64::from(m.width) * u64::from(m.height);
+        let best = match self {
+            ModeSpec::Preferred => modes.first(),
+            ModeSpec::HighRes => modes.iter().max_by(|a, b| {
+                area(a)
+                    .cmp(&area(b))
+                    .then(a.refresh_rate.total_cmp(&b.refresh_rate))
+            }),
+            ModeSpec::HighRr => modes.iter().max_by(|a, b| {
+                a.refresh_rate
+                    .total_cmp(&b.refresh_rate)
+                    .then(area(a).cmp(&area(b)))
+            }),
+            ModeSpec::MaxWidth => modes.iter().max_by(|a, b| {
+                a.width
+                    .cmp(&b.width)
+                    .then(a.refresh_rate.total_cmp(&b.refresh_rate))
+            }),
+            ModeSpec::Explicit {
+                resolution,
+                refresh_rate,
+            } => {
+                let hz = refresh_rate.or_else(|| {
+                    modes
+                        .iter()
+                        .filter(|m| m.resolution() == *resolution)
+                        .map(|m| m.refresh_rate)
+                        .max_by(f32::total_cmp)
+                });
+                return Some((resolution.clone(), hz.unwrap_or(60.0)));
+            }
+        };
+        best.map(|m| (m.resolution(), m.refresh_rate))
+    }
+}
+
+impl std::fmt::Display for ModeSpec {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ModeSpec::Preferred => write!(f, "preferred"),
+            ModeSpec::HighRes => write!(f, "highres"),
+            ModeSpec::HighRr => write!(f, "highrr"),
+            ModeSpec::MaxWidth => write!(f, "maxwidth"),
+            ModeSpec::Explicit {
+                resolution,
+                refresh_rate: Some(hz),
+            } => write!(f, "{}@{}", resolution, hz),
+            ModeSpec::Explicit { resolution, .. } => write!(f, "{}", resolution),
+        }
+    }
+}
+
+impl TryFrom<String> for ModeSpec {
+    type Error = String;
+
+    fn try_from(s: String) -> Result<Self, Self::Error> {
+        ModeSpec::parse(&s).ok_or_else(|| format!("invalid mode '{}'", s))
+    }
+}
+
+impl From<ModeSpec> for String {
+    fn from(spec: ModeSpec) -> Self {
+        spec.to_string()
+    }
+}
+
+/// Position field of a monitor rule: fixed or placed automatically by Hyprland.
+/// Serialized as `[x, y]` or as the symbolic name.
+#[derive(Clone, Debug, PartialEq, Default, Serialize, Deserialize)]
+#[serde(try_from = "PositionRepr", into = "PositionRepr")]
+pub enum PositionSpec {
+    #[default]
+    Auto,
+    AutoRight,
+    AutoLeft,
+    AutoUp,
+    AutoDown,
+    Explicit(Position),
+}
+
+impl PositionSpec {
+    /// Parse `auto`, `auto-right|left|up|down` or `XxY`.
+    pub fn parse(s: &str) -> Option<Self> {
+        match s {
+            "auto" => Some(PositionSpec::Auto),
+            "auto-right" => Some(PositionSpec::AutoRight),
+            "auto-left" => Some(PositionSpec::AutoLeft),
+            "auto-up" => Some(PositionSpec::AutoUp),
+            "auto-down" => Some(PositionSpec::AutoDown),
+            _ => {
+                let (x, y) = s.split_once('x')?;
+                Some(PositionSpec::Explicit(Position {
+                    x: x.parse().ok()?,
+                    y: y.parse().ok()?,
+                }))
+            }
+        }
+    }
+
+    /// Whether Hyprland places the monitor (anything but `Explicit`).
+    pub fn is_symbolic(&self) -> bool {
+        !matches!(self, PositionSpec::Explicit(_))
+    }
+}
+
+impl std::fmt::Display for PositionSpec {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            PositionSpec::Auto => write!(f, "auto"),
+            PositionSpec::AutoRight => write!(f, "auto-right"),
+            PositionSpec::AutoLeft => write!(f, "auto-left"),
+            PositionSpec::AutoUp => write!(f, "auto-up"),
+            PositionSpec::AutoDown => write!(f, "auto-down"),
+            PositionSpec::Explicit(p) => write!(f, "{}x{}", p.x, p.y),
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize)]
+#[serde(untagged)]
+enum PositionRepr {
+    Fixed([i32; 2]),
+    Symbolic(String),
+}
+
+impl TryFrom<PositionRepr> for PositionSpec {
+    type Error = String;
+
+    fn try_from(repr: PositionRepr) -> Result<Self, Self::Error> {
+        match repr {
+            PositionRepr::Fixed([x, y]) => Ok(PositionSpec::Explicit(Position { x, y })),
+            PositionRepr::Symbolic(s) => {
+                PositionSpec::parse(&s).ok_or_else(|| format!("invalid position '{}'", s))
+            }
+        }
+    }
+}
+
+impl From<PositionSpec> for PositionRepr {
+    fn from(spec: PositionSpec) -> Self {
+        match spec {
+            PositionSpec::Explicit(p) => PositionRepr::Fixed([p.x, p.y]),
+            other => PositionRepr::Symbolic(other.to_string()),
+        }
+    }
+}
+
+/// Variable refresh rate mode (`vrr,N`).
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Vrr {
+    Off,
+    On,
+    FullscreenOnly,
+}
+
+impl Vrr {
+    /// Hyprland value: 0 off, 1 on, 2 fullscreen only.
+    pub fn to_hyprland_str(self) -> &'static str {
+        match self {
+            Vrr::Off => "0",
+            Vrr::On => "1",
+            Vrr::FullscreenOnly => "2",
+        }
+    }
+
+    /// Parse the Hyprland value.
+    pub fn from_hyprland_str(s: &str) -> Option<Self> {
+        match s {
+            "0" => Some(Vrr::Off),
+            "1" => Some(Vrr::On),
+            "2" => Some(Vrr::FullscreenOnly),
+            _ => None,
+        }
+    }
+
+    /// Cycle Hyprland default -> off -> on -> fullscreen only -> default.
+    pub fn cycle(current: Option<Self>) -> Option<Self> {
+        match current {
+            None => Some(Vrr::Off),
+            Some(Vrr::Off) => Some(Vrr::On),
+            Some(Vrr::On) => Some(Vrr::FullscreenOnly),
+            Some(Vrr::FullscreenOnly) => None,
+        }
+    }
+}
+
+impl std::fmt::Display for Vrr {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let s = match self {
+            Vrr::Off => "Off",
+            Vrr::On => "On",
+            Vrr::FullscreenOnly => "Fullscreen only",
+        };
+        write!(f, "{}", s)
+    }
+}
+
+/// Accepted `sdrbrightness` values.
+pub const SDR_BRIGHTNESS_RANGE: std::ops::RangeInclusive<f32> = 0.5..=2.0;
+/// Accepted `sdrsaturation` values.
+pub const SDR_SATURATION_RANGE: std::ops::RangeInclusive<f32> = 0.0..=2.0;
+
+/// Colour management preset (`cm,NAME`).
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ColorMode {
+    Srgb,
+    Wide,
+    Hdr,
+    HdrEdid,
+}
+
+impl ColorMode {
+    /// Hyprland name of the preset.
+    pub fn to_hyprland_str(self) -> &'static str {
+        match self {
+            ColorMode::Srgb => "srgb",
+            ColorMode::Wide => "wide",
+            ColorMode::Hdr => "hdr",
+            ColorMode::HdrEdid => "hdredid",
+        }
+    }
+
+    /// Parse a Hyprland preset name.
+    pub fn from_hyprland_str(s: &str) -> Option<Self> {
+        match s {
+            "srgb" => Some(ColorMode::Srgb),
+            "wide" => Some(ColorMode::Wide),
+            "hdr" => Some(ColorMode::Hdr),
+            "hdredid" => Some(ColorMode::HdrEdid),
+            _ => None,
+        }
+    }
+
+    /// Whether SDR brightness and saturation apply.
+    pub fn is_hdr(self) -> bool {
+        matches!(self, ColorMode::Hdr | ColorMode::HdrEdid)
+    }
+
+    /// Cycle Hyprland default -> sRGB -> wide -> HDR -> HDR (EDID) -> default.
+    pub fn cycle(current: Option<Self>) -> Option<Self> {
+        match current {
+            None => Some(ColorMode::Srgb),
+            Some(ColorMode::Srgb) => Some(ColorMode::Wide),
+            Some(ColorMode::Wide) => Some(ColorMode::Hdr),
+            Some(ColorMode::Hdr) => Some(ColorMode::HdrEdid),
+            Some(ColorMode::HdrEdid) => None,
+        }
+    }
+}
+
+impl std::fmt::Display for ColorMode {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let s = match self {
+            ColorMode::Srgb => "sRGB",
+            ColorMode::Wide => "Wide gamut",
+            ColorMode::Hdr => "HDR",
+            ColorMode::HdrEdid => "HDR (EDID)",
+        };
+        write!(f, "{}", s)
+    }
+}
+
+/// Parse `sdrbrightness` / `sdrsaturation`, rejecting values outside `range`.
+pub fn parse_sdr_value(
+    key: &str,
+    value: &str,
+    range: std::ops::RangeInclusive<f32>,
+) -> Result<f32, String> {
+    match value.parse::<f32>() {
+        Ok(v) if range.contains(&v) => Ok(v),
+        _ => Err(format!(
+            "Invalid {} '{}' (expected {} to {})",
+            key,
+            value,
+            range.start(),
+            range.end()
+        )),
+    }
+}
+
 /// Display transform options.
 #[derive(Clone, Debug, Serialize, Deserialize, PartialEq, Default)]
 pub enum Transform {
@@ -64,6 +561,17 @@ pub enum Transform {
 }
 
 impl Transform {
+    /// Whether the output is turned by 90 or 270 degrees (width and height swap).
+    pub fn is_rotated(&self) -> bool {
+        matches!(
+            self,
+            Transform::Rotate90
+                | Transform::Rotate270
+                | Transform::Flipped90
+                | Transform::Flipped270
+        )
+    }
+
     /// Hyprland transform string.
     pub fn to_hyprland_str(&self) -> &'static str {
         match self {
@@ -124,9 +632,31 @@ impl std::fmt::Display for Transform {
 }
 
 /// Mode from Hyprland (resolution + refresh rate).
-#[derive(Clone, Debug)]
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq)]
 pub struct Mode {
     pub width: u32,
     pub height: u32,
     pub refresh_rate: f32,
 }
+
+impl Mode {
+    /// Resolution part of the mode.
+    pub fn resolution(&self) -> Resolution {
+        Resolution {
+            width: self.width,
+            height: self.height,
+        }
+    }
+}
+
+/// Unique resolutions in the order they first appear in `modes`.
+pub fn unique_resolutions(modes: &[Mode]) -> Vec<Resolution> {
+    let mut out: Vec<Resolution> = Vec::new();
+    for m in modes {
+        let res = m.resolution();
+        if !out.contains(&res) {
+            out.push(res);
+        }
+    }
+    out
+}
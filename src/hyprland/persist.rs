@@ -0,0 +1,243 @@
+//! Persisting generated monitor rules into Hyprland's config files.
+
+use anyhow::{bail, Context, Result};
+use serde::{Deserialize, Serialize};
+use std::path::{Path, PathBuf};
+
+/// First line of the region managed in hyprland.conf.
+pub const BEGIN_MARKER: &str = "# BEGIN hypr-monitor-tui";
+/// Last line of the region managed in hyprland.conf.
+pub const END_MARKER: &str = "# END hypr-monitor-tui";
+
+/// How generated monitor rules are written.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum PersistMode {
+    /// Write a dedicated monitors.conf and `source =` it from hyprland.conf
+    #[default]
+    Source,
+    /// Replace the marked region inside hyprland.conf
+    Managed,
+}
+
+/// Files touched when persisting.
+#[derive(Clone, Debug, PartialEq)]
+pub struct PersistTarget {
+    pub mode: PersistMode,
+    pub hyprland_conf: PathBuf,
+    /// Only used in `Source` mode
+    pub monitors_conf: PathBuf,
+}
+
+impl PersistTarget {
+    /// Target with the default paths under ~/.config/hypr.
+    pub fn new(mode: PersistMode) -> Self {
+        let dir = dirs::config_dir()
+            .unwrap_or_else(|| PathBuf::from("."))
+            .join("hypr");
+        Self {
+            mode,
+            hyprland_conf: dir.join("hyprland.conf"),
+            monitors_conf: dir.join("monitors.conf"),
+        }
+    }
+}
+
+/// Result of a successful persist.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct PersistReport {
+    /// Files whose content changed
+    pub written: Vec<PathBuf>,
+    /// Backups of the previous file contents
+    pub backups: Vec<PathBuf>,
+}
+
+/// Write the generated `block` according to `target`.
+pub fn persist(target: &PersistTarget, block: &str) -> Result<PersistReport> {
+    let mut report = PersistReport::default();
+    let mut write = |path: &Path, content: &str| -> Result<()> {
+        if std::fs::read_to_string(path).is_ok_and(|old| old == content) {
+            return Ok(());
+        }
+        if let Some(backup) = write_atomic(path, content)? {
+            report.backups.push(backup);
+        }
+        report.written.push(path.to_path_buf());
+        Ok(())
+    };
+    let conf = read_optional(&target.hyprland_conf)?;
+    match target.mode {
+        PersistMode::Source => {
+            write(&target.monitors_conf, &with_newline(block))?;
+            if let Some(updated) = ensure_source_line(&conf, &target.monitors_conf) {
+                write(&target.hyprland_conf, &updated)?;
+            }
+        }
+        PersistMode::Managed => {
+            let updated = replace_managed_block(&conf, block)?;
+            write(&target.hyprland_conf, &updated)?;
+        }
+    }
+    Ok(report)
+}
+
+fn read_optional(path: &Path) -> Result<String> {
+    match std::fs::read_to_string(path) {
+        Ok(s) => Ok(s),
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(String::new()),
+        Err(e) => Err(e).with_context(|| format!("Failed to read {}", path.display())),
+    }
+}
+
+fn with_newline(s: &str) -> String {
+    if s.ends_with('\n') {
+        s.to_string()
+    } else {
+        format!("{}\n", s)
+    }
+}
+
+/// Replace the region between the markers with `block`, or append a new region.
+pub fn replace_managed_block(content: &str, block: &str) -> Result<String> {
+    let lines: Vec<&str> = content.lines().collect();
+    let begin = lines.iter().position(|l| l.trim() == BEGIN_MARKER);
+    let end = lines.iter().position(|l| l.trim() == END_MARKER);
+    let region = format!("{}\n{}{}", BEGIN_MARKER, with_newline(block), END_MARKER);
+    let out = match (begin, end) {
+        (Some(b), Some(e)) if b < e => {
+            let mut out: Vec<String> = lines[..b].iter().map(|l| l.to_string()).collect();
+            out.push(region);
+            out.extend(lines[e + 1..].iter().map(|l| l.to_string()));
+            out.join("\n")
+        }
+        (None, None) if content.trim().is_empty() => region,
+        (None, None) => format!("{}\n\n{}", content.trim_end(), region),
+        _ => bail!("Unbalanced '{}' / '{}' markers", BEGIN_MARKER, END_MARKER),
+    };
+    Ok(with_newline(&out))
+}
+
+/// Expand a leading `~/` to the home directory.
+pub fn expand_home(path: &str) -> PathBuf {
+    match (path.strip_prefix("~/"), dirs::home_dir()) {
+        (Some(rest), Some(home)) => home.join(rest),
+        _ => PathBuf::from(path),
+    }
+}
+
+/// Append `source = <file>` unless hyprland.conf already sources it.
+/// Returns the updated content, or None if nothing changes.
+pub fn ensure_source_line(content: &str, file: &Path) -> Option<String> {
+    let present = content.lines().any(|l| {
+        let Some((key, value)) = l.split_once('=') else {
+            return false;
+        };
+        key.trim() == "source" && expand_home(value.trim()) == file
+    });
+    if present {
+        return None;
+    }
+    let line = format!("source = {}", file.display());
+    Some(if content.trim().is_empty() {
+        with_newline(&line)
+    } else {
+        format!("{}\n\n{}\n", content.trim_end(), line)
+    })
+}
+
+/// Replace `path` atomically (temp file + rename), backing up the previous file.
+/// Symlinks are followed so the link itself is kept, and the previous file mode is preserved.
+/// Returns the backup path if there was a previous file.
+pub fn write_atomic(path: &Path, content: &str) -> Result<Option<PathBuf>> {
+    let dir = parent_dir(path);
+    std::fs::create_dir_all(dir).with_context(|| format!("Failed to create {}", dir.display()))?;
+    let name = file_name(path)?;
+
+    let backup = if path.exists() {
+        let backup = backup_path(dir, &name);
+        std::fs::copy(path, &backup)
+            .with_context(|| format!("Failed to back up {}", path.display()))?;
+        Some(backup)
+    } else {
+        None
+    };
+
+    let target = std::fs::canonicalize(path).unwrap_or_else(|_| path.to_path_buf());
+    let target_dir = parent_dir(&target);
+    let tmp = target_dir.join(format!(".{}.tmp", file_name(&target)?));
+    {
+        use std::io::Write;
+        let mut f = std::fs::File::create(&tmp)
+            .with_context(|| format!("Failed to create {}", tmp.display()))?;
+        f.write_all(content.as_bytes())?;
+        f.sync_all()?;
+    }
+    if let Ok(meta) = std::fs::metadata(&target) {
+        std::fs::set_permissions(&tmp, meta.permissions())
+            .with_context(|| format!("Failed to set permissions on {}", tmp.display()))?;
+    }
+    std::fs::rename(&tmp, &target)
+        .with_context(|| format!("Failed to replace {}", target.display()))?;
+    Ok(backup)
+}
+
+fn parent_dir(path: &Path) -> &Path {
+    path.parent()
+        .filter(|d| !d.as_os_str().is_empty())
+        .unwrap_or(Path::new("."))
+}
+
+fn file_name(path: &Path) -> Result<String> {
+    Ok(path
+        .file_name()
+        .with_context(|| format!("Not a file path: {}", path.display()))?
+        .to_string_lossy()
+        .into_owned())
+}
+
+/// Timestamped backup path in `dir` that does not exist yet.
+fn backup_path(dir: &Path, file_name: &str) -> PathBuf {
+    let stamp = chrono::Local::now().format("%Y%m%d-%H%M%S%.3f");
+    let mut backup = dir.join(format!("{}.{}.bak", file_name, stamp));
+    let mut n = 1;
+    while backup.exists() {
+        backup = dir.join(format!("{}.{}-{}.bak", file_name, stamp, n));
+        n += 1;
+    }
+    backup
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_replace_managed_block_in_place() {
+        let conf = "exec-once = waybar\n# BEGIN hypr-monitor-tui\nmonitor=DP-1,disable\n# END hypr-monitor-tui\ninput {\n}\n";
+        let out = replace_managed_block(conf, "monitor=DP-1,preferred,auto,1").unwrap();
+        assert_eq!(
+            out,
+            "exec-once = waybar\n# BEGIN hypr-monitor-tui\nmonitor=DP-1,preferred,auto,1\n# END hypr-monitor-tui\ninput {\n}\n"
+        );
+    }
+
+    #[test]
+    fn test_replace_managed_block_appends_and_rejects_unbalanced() {
+        let out = replace_managed_block("exec-once = waybar\n", "monitor=DP-1,disable").unwrap();
+        assert_eq!(
+            out,
+            "exec-once = waybar\n\n# BEGIN hypr-monitor-tui\nmonitor=DP-1,disable\n# END hypr-monitor-tui\n"
+        );
+        assert!(replace_managed_block("# BEGIN hypr-monitor-tui\n", "x").is_err());
+    }
+
+    #[test]
+    fn test_ensure_source_line() {
+        let file = Path::new("/home/u/.config/hypr/monitors.conf");
+        assert_eq!(
+            ensure_source_line("exec-once = waybar\n", file).unwrap(),
+            "exec-once = waybar\n\nsource = /home/u/.config/hypr/monitors.conf\n"
+        );
+        assert!(ensure_source_line("source=/home/u/.config/hypr/monitors.conf\n", file).is_none());
+    }
+}
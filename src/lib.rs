@@ -2,6 +2,7 @@
 
 pub mod app;
 pub mod config;
+pub mod daemon;
 pub mod events;
 pub mod hyprland;
 pub mod ui;
@@ -4,14 +4,21 @@
 
 use anyhow::{Context, Result};
 use clap::Parser;
+use crossterm::event::{
+    DisableMouseCapture, EnableMouseCapture, KeyCode, KeyEvent, KeyModifiers, MouseButton,
+    MouseEvent, MouseEventKind,
+};
+use crossterm::terminal::{
+    disable_raw_mode, enable_raw_mode, EnterAlternateScreen, LeaveAlternateScreen,
+};
 use hypr_monitor_tui::app::{App, AppMode, ConfirmAction, EditField};
 use hypr_monitor_tui::config;
+use hypr_monitor_tui::daemon;
 use hypr_monitor_tui::events;
-use hypr_monitor_tui::hyprland;
+use hypr_monitor_tui::hyprland::{self, Arrangement, MonitorBackend};
 use hypr_monitor_tui::ui;
-use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
-use crossterm::terminal::{disable_raw_mode, enable_raw_mode, EnterAlternateScreen, LeaveAlternateScreen};
 use ratatui::backend::CrosstermBackend;
+use ratatui::layout::Rect;
 use ratatui::Terminal;
 use std::io;
 use std::path::PathBuf;
@@ -33,35 +40,77 @@ fn main() -> Result<()> {
         )
         .init();
 
-    if cli.apply.is_some() && !cli.dry_run {
-        return apply_profile(&cli);
+    let client = hyprland::HyprlandClient::new();
+    // The CLI modes need a running compositor; the TUI starts without one.
+    let needs_hyprland = cli.daemon || cli.apply.is_some() || cli.export || cli.validate.is_some();
+    if needs_hyprland && !hyprland::HyprlandClient::is_available() {
+        anyhow::bail!(
+            "Hyprland is not running or not reachable. Run hypr-monitor-tui inside a Hyprland session."
+        );
+    }
+    if cli.daemon {
+        return run_daemon(&cli, &client);
+    }
+    if cli.apply.is_some() {
+        return apply_profile(&cli, &client);
     }
     if cli.export {
-        return export_config(&cli);
+        return export_config(&cli, &client);
     }
     if cli.list_profiles {
         return list_profiles(&cli);
     }
-    run_tui(&cli)
+    if let Some(path) = &cli.import {
+        return import_config(&cli, path);
+    }
+    if let Some(name) = &cli.validate {
+        return validate_profile(&cli, &client, name);
+    }
+    run_tui(&cli, &client)
 }
 
-fn apply_profile(cli: &Cli) -> Result<()> {
-    if !hyprland::HyprlandClient::is_available() {
-        anyhow::bail!(
-            "Hyprland is not running or not reachable. Run hypr-monitor-tui inside a Hyprland session."
-        );
+fn apply_profile(cli: &Cli, backend: &dyn MonitorBackend) -> Result<()> {
+    let profile_name = cli
+        .apply
+        .as_deref()
+        .context("--apply requires profile name")?;
+    let profile = load_named_profile(cli, profile_name)?;
+    let current = backend
+        .get_monitors()
+        .context("Failed to get current monitors")?;
+    let matched = profile.match_monitors(&current);
+    for missing in &matched.unmatched {
+        eprintln!("Warning: profile monitor not connected: {}", missing);
+    }
+    for m in matched.monitors.iter().filter(|m| m.enabled) {
+        if !hyprland::is_valid_scale(&m.resolution, m.scale) {
+            let near: Vec<String> = hyprland::valid_scales_near(&m.resolution, m.scale, 3)
+                .iter()
+                .map(|s| {
+                    format!("{:.4}", s)
+                        .trim_end_matches('0')
+                        .trim_end_matches('.')
+                        .to_string()
+                })
+                .collect();
+            eprintln!(
+                "Warning: scale {} is not valid for {} at {}; Hyprland will pick another (try {})",
+                m.scale,
+                m.name,
+                m.resolution,
+                near.join(", ")
+            );
+        }
+    }
+    let monitors = matched.monitors;
+    let issues = hyprland::validate_layout(&monitors);
+    print_layout_issues(&issues);
+    if issues
+        .iter()
+        .any(|i| i.severity == hyprland::Severity::Error)
+    {
+        anyhow::bail!("Profile {} has layout errors, not applied", profile_name);
     }
-    let profile_name = cli.apply.as_deref().context("--apply requires profile name")?;
-    let config_dir = cli
-        .config
-        .parent()
-        .unwrap_or_else(|| std::path::Path::new("."));
-    let profile_path = config_dir.join("profiles").join(format!("{}.toml", profile_name));
-    let profile = config::load_profile(&profile_path)
-        .with_context(|| format!("Failed to load profile: {}", profile_name))?;
-    let client = hyprland::HyprlandClient::new();
-    let current = client.get_monitors().context("Failed to get current monitors")?;
-    let monitors = profile.to_monitors(&current);
     if cli.dry_run {
         println!("Would apply {} monitors from profile {}", monitors.len(), profile_name);
         for m in &monitors {
@@ -69,24 +118,110 @@ fn apply_profile(cli: &Cli) -> Result<()> {
         }
         return Ok(());
     }
-    client.apply_all(&monitors).context("Failed to apply configuration")?;
+    backend
+        .apply_all(&monitors)
+        .context("Failed to apply configuration")?;
+    if cli.confirm {
+        let timeout = config::load_config(&cli.config)
+            .map(|c| c.general.preview_timeout)
+            .unwrap_or_else(|_| config::GeneralSettings::default().preview_timeout);
+        if !confirm_on_stdin(timeout) {
+            backend
+                .apply_all(&current)
+                .context("Failed to revert configuration")?;
+            println!("Not confirmed, reverted to previous layout.");
+            return Ok(());
+        }
+    }
     println!("Applied profile: {}", profile_name);
     Ok(())
 }
 
-fn export_config(_cli: &Cli) -> Result<()> {
-    if !hyprland::HyprlandClient::is_available() {
-        anyhow::bail!(
-            "Hyprland is not running or not reachable. Run hypr-monitor-tui inside a Hyprland session."
-        );
+/// Load `profiles/<name>.toml` next to the config file.
+fn load_named_profile(cli: &Cli, name: &str) -> Result<config::Profile> {
+    let config_dir = cli
+        .config
+        .parent()
+        .unwrap_or_else(|| std::path::Path::new("."));
+    let profile_path = config_dir.join("profiles").join(format!("{}.toml", name));
+    config::load_profile(&profile_path).with_context(|| format!("Failed to load profile: {}", name))
+}
+
+fn print_layout_issues(issues: &[hyprland::LayoutIssue]) {
+    for issue in issues {
+        let level = match issue.severity {
+            hyprland::Severity::Error => "Error",
+            hyprland::Severity::Warning => "Warning",
+        };
+        eprintln!("{}: {}", level, issue);
     }
-    let client = hyprland::HyprlandClient::new();
-    let monitors = client.get_monitors().context("Failed to get monitors")?;
-    let out = hyprland::generate_config(&monitors);
+}
+
+/// Check a profile's layout against the connected monitors; fails on errors.
+fn validate_profile(cli: &Cli, backend: &dyn MonitorBackend, name: &str) -> Result<()> {
+    let profile = load_named_profile(cli, name)?;
+    let current = backend
+        .get_monitors()
+        .context("Failed to get current monitors")?;
+    let matched = profile.match_monitors(&current);
+    for missing in &matched.unmatched {
+        eprintln!("Warning: profile monitor not connected: {}", missing);
+    }
+    let issues = hyprland::validate_layout(&matched.monitors);
+    print_layout_issues(&issues);
+    if issues
+        .iter()
+        .any(|i| i.severity == hyprland::Severity::Error)
+    {
+        anyhow::bail!("Profile {} has layout errors", name);
+    }
+    println!("Profile {} is valid ({} warnings)", name, issues.len());
+    Ok(())
+}
+
+/// Ask on the terminal whether to keep the applied layout; false on "no" or timeout.
+fn confirm_on_stdin(timeout_secs: u64) -> bool {
+    println!(
+        "Keep this monitor layout? [y/N] (reverting in {} s)",
+        timeout_secs
+    );
+    let (tx, rx) = std::sync::mpsc::channel();
+    std::thread::spawn(move || {
+        let mut line = String::new();
+        if io::stdin().read_line(&mut line).is_ok() {
+            let _ = tx.send(line);
+        }
+    });
+    match rx.recv_timeout(Duration::from_secs(timeout_secs)) {
+        Ok(line) => matches!(line.trim(), "y" | "Y" | "yes"),
+        Err(_) => false,
+    }
+}
+
+fn export_config(cli: &Cli, backend: &dyn MonitorBackend) -> Result<()> {
+    let monitors = backend.get_monitors().context("Failed to get monitors")?;
+    let options = hyprland::ExportOptions {
+        by_description: cli.by_description,
+        syntax: cli.syntax,
+    };
+    let out = hyprland::generate_config_with(&monitors, &options);
     print!("{}", out);
     Ok(())
 }
 
+fn run_daemon(cli: &Cli, backend: &dyn MonitorBackend) -> Result<()> {
+    let config_dir = cli
+        .config
+        .parent()
+        .unwrap_or_else(|| std::path::Path::new("."));
+    let opts = daemon::DaemonOptions {
+        profiles_dir: config_dir.join("profiles"),
+        debounce: Duration::from_millis(cli.debounce_ms),
+        dry_run: cli.dry_run,
+    };
+    daemon::run(backend, &opts)
+}
+
 fn list_profiles(cli: &Cli) -> Result<()> {
     let config_dir = cli.config.parent().unwrap_or_else(|| std::path::Path::new("."));
     let profiles_dir = config_dir.join("profiles");
@@ -97,13 +232,48 @@ fn list_profiles(cli: &Cli) -> Result<()> {
     Ok(())
 }
 
-fn run_tui(cli: &Cli) -> Result<()> {
-    let client = hyprland::HyprlandClient::new();
+fn import_config(cli: &Cli, path: &std::path::Path) -> Result<()> {
+    let name = cli.name.as_deref().unwrap_or("imported");
+    config::validate_profile_name(name).map_err(anyhow::Error::msg)?;
+    let rules = hyprland::read_monitor_rules(path)?;
+    let profile = config::Profile::from_rules(
+        name.to_string(),
+        Some(format!("Imported from {}", path.display())),
+        &rules,
+    );
+    if profile.monitors.is_empty() {
+        anyhow::bail!("No monitor= rules found in {}", path.display());
+    }
+    if cli.dry_run {
+        print!("{}", toml::to_string_pretty(&profile)?);
+        return Ok(());
+    }
+    let config_dir = cli
+        .config
+        .parent()
+        .unwrap_or_else(|| std::path::Path::new("."));
+    let profiles_dir = config_dir.join("profiles");
+    let target = profiles_dir.join(format!("{}.toml", name));
+    if target.exists() {
+        anyhow::bail!(
+            "Profile '{}' already exists; choose another name with --name",
+            name
+        );
+    }
+    std::fs::create_dir_all(&profiles_dir)
+        .with_context(|| format!("Failed to create {}", profiles_dir.display()))?;
+    config::save_profile(&target, &profile)?;
+    println!(
+        "Imported {} monitor(s) into profile '{}'",
+        profile.monitors.len(),
+        name
+    );
+    Ok(())
+}
+
+fn run_tui(cli: &Cli, backend: &dyn MonitorBackend) -> Result<()> {
     let monitors = if hyprland::HyprlandClient::is_available() {
-        match std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| {
-            let c = hyprland::HyprlandClient::new();
-            c.get_monitors()
-        })) {
+        match std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| backend.get_monitors())) {
             Ok(Ok(m)) => m,
             Ok(Err(e)) => {
                 tracing::warn!("Could not get monitors: {}", e);
@@ -120,26 +290,30 @@ fn run_tui(cli: &Cli) -> Result<()> {
     };
 
     let mut theme = config::Theme::default();
-    if cli.config.exists() {
-        if let Ok(s) = std::fs::read_to_string(&cli.config) {
-            if let Ok(cfg) = toml::from_str::<config::AppConfig>(&s) {
-                cfg.theme.apply_to(&mut theme);
-            }
-        }
-    }
+    let cfg = config::load_config(&cli.config).unwrap_or_else(|e| {
+        tracing::warn!("Could not load config: {}", e);
+        config::AppConfig::default()
+    });
+    cfg.theme.apply_to(&mut theme);
 
     let mut app = App::new(monitors, cli.config.clone(), theme);
+    app.settings = cfg.general;
     enable_raw_mode().context("Failed to enable raw mode")?;
     let mut stdout = io::stdout();
-    crossterm::execute!(stdout, EnterAlternateScreen).context("Failed to enter alternate screen")?;
-    let backend = CrosstermBackend::new(stdout);
-    let mut terminal = Terminal::new(backend).context("Failed to create terminal")?;
+    crossterm::execute!(stdout, EnterAlternateScreen, EnableMouseCapture)
+        .context("Failed to enter alternate screen")?;
+    let mut terminal =
+        Terminal::new(CrosstermBackend::new(stdout)).context("Failed to create terminal")?;
 
-    let res = run_loop(&mut terminal, &mut app, &client);
+    let res = run_loop(&mut terminal, &mut app, backend);
 
     disable_raw_mode().context("Failed to disable raw mode")?;
-    crossterm::execute!(terminal.backend_mut(), LeaveAlternateScreen)
-        .context("Failed to leave alternate screen")?;
+    crossterm::execute!(
+        terminal.backend_mut(),
+        LeaveAlternateScreen,
+        DisableMouseCapture
+    )
+    .context("Failed to leave alternate screen")?;
     terminal.show_cursor().context("Failed to show cursor")?;
 
     if let Err(e) = res {
@@ -152,43 +326,105 @@ fn run_tui(cli: &Cli) -> Result<()> {
 fn run_loop(
     terminal: &mut Terminal<CrosstermBackend<io::Stdout>>,
     app: &mut App,
-    client: &hyprland::HyprlandClient,
+    backend: &dyn MonitorBackend,
 ) -> Result<()> {
     let mut event_handler = events::EventHandler::new(Duration::from_millis(100));
+    match backend.subscribe_events() {
+        Ok(rx) => event_handler.forward_monitor_events(rx),
+        Err(e) => tracing::warn!("Monitor hotplug events unavailable: {}", e),
+    }
+    let mut drag = None;
     loop {
         terminal.draw(|f| ui::draw(f, app, &app.theme))?;
         let ev = event_handler.recv_event().map_err(anyhow::Error::msg)?;
         match ev {
-            events::AppEvent::Key(key) => {
-                if handle_key(app, client, key) {
-                    break;
-                }
+            events::AppEvent::Key(key) if handle_key(app, backend, key) => break,
+            events::AppEvent::Mouse(mouse) => {
+                let size = terminal.size()?;
+                let screen = Rect::new(0, 0, size.width, size.height);
+                handle_mouse(app, mouse, screen, &mut drag);
             }
             events::AppEvent::Resize(_, _) => {}
             events::AppEvent::Tick => {
-                app.clear_messages();
+                let now = std::time::Instant::now();
+                app.expire_messages(now);
+                if app.revert_due(now) {
+                    app.revert_live(backend);
+                }
             }
+            events::AppEvent::MonitorChange => app.refresh_monitors(backend),
             _ => {}
         }
     }
     Ok(())
 }
 
-fn handle_key(
-    app: &mut App,
-    client: &hyprland::HyprlandClient,
-    key: KeyEvent,
-) -> bool {
+/// Monitor being dragged with the mouse.
+struct Drag {
+    /// Grid mapping frozen at drag start so the view doesn't rescale under the cursor
+    view: ui::GridView,
+    /// Offset from the monitor origin to the grabbed pixel
+    grab: (i32, i32),
+}
+
+fn handle_mouse(app: &mut App, mouse: MouseEvent, screen: Rect, drag: &mut Option<Drag>) {
+    if !matches!(app.mode, AppMode::Normal | AppMode::Moving) {
+        return;
+    }
+    match mouse.kind {
+        MouseEventKind::Down(MouseButton::Left) => {
+            let view = ui::GridView::new(&app.monitors, ui::grid_area(screen));
+            let Some(idx) = ui::monitor_at(&app.monitors, &view, mouse.column, mouse.row) else {
+                return;
+            };
+            if app.mode == AppMode::Moving {
+                app.commit_move();
+            }
+            app.selected_monitor = idx;
+            app.begin_move();
+            let (x, y) = view.to_layout(mouse.column, mouse.row);
+            let pos = &app.monitors[idx].position;
+            *drag = Some(Drag {
+                view,
+                grab: (x - pos.x, y - pos.y),
+            });
+        }
+        MouseEventKind::Drag(MouseButton::Left) => {
+            if let Some(d) = drag {
+                let (x, y) = d.view.to_layout(mouse.column, mouse.row);
+                app.place_selected(x - d.grab.0, y - d.grab.1);
+            }
+        }
+        MouseEventKind::Up(MouseButton::Left) => {
+            if let Some(d) = drag.take() {
+                // Snap within roughly two cells of a neighbour edge.
+                let threshold = ((2.0 / d.view.scale).round() as i32).max(1);
+                app.snap_selected(threshold);
+                app.commit_move();
+            }
+        }
+        _ => {}
+    }
+}
+
+fn handle_key(app: &mut App, backend: &dyn MonitorBackend, key: KeyEvent) -> bool {
     let code = key.code;
     let mods = key.modifiers;
 
     match &app.mode {
         AppMode::Confirm { action, .. } => {
+            // Profile deletion is asked from the profile manager; return there.
+            let back = if matches!(action, ConfirmAction::DeleteProfile(_)) {
+                AppMode::ProfileSelect
+            } else {
+                AppMode::Normal
+            };
             if code == KeyCode::Char('y') || code == KeyCode::Char('Y') {
-                confirm_yes(app, client, action.clone());
-                app.mode = AppMode::Normal;
+                let action = action.clone();
+                app.mode = back;
+                confirm_yes(app, backend, action);
             } else if code == KeyCode::Char('n') || code == KeyCode::Char('N') || code == KeyCode::Esc {
-                app.mode = AppMode::Normal;
+                app.mode = back;
             }
             return false;
         }
@@ -199,14 +435,52 @@ fn handle_key(
             return false;
         }
         AppMode::ProfileSelect => {
-            if code == KeyCode::Esc {
-                app.mode = AppMode::Normal;
+            handle_profile_key(app, backend, code);
+            return false;
+        }
+        AppMode::Prompt => {
+            handle_prompt_key(app, code);
+            return false;
+        }
+        AppMode::Editing { field } => {
+            let field = *field;
+            handle_edit_key(app, field, code);
+            return false;
+        }
+        AppMode::Moving => {
+            handle_move_key(app, code, mods);
+            return false;
+        }
+        AppMode::KeepSettings { .. } => {
+            match code {
+                KeyCode::Char('y') | KeyCode::Char('Y') | KeyCode::Enter => {
+                    app.keep_applied();
+                    app.set_status("Applied.".to_string());
+                }
+                KeyCode::Char('n') | KeyCode::Char('N') | KeyCode::Esc => {
+                    app.revert_live(backend);
+                }
+                _ => {}
             }
             return false;
         }
         _ => {}
     }
 
+    if app.arrange_pending {
+        match code {
+            KeyCode::Char('r') => app.arrange(Arrangement::RowTop),
+            KeyCode::Char('c') => app.arrange(Arrangement::RowCentered),
+            KeyCode::Char('v') => app.arrange(Arrangement::Column),
+            KeyCode::Char('l') => app.arrange(Arrangement::LShape),
+            _ => {
+                app.arrange_pending = false;
+                app.clear_messages();
+            }
+        }
+        return false;
+    }
+
     if code == KeyCode::Char('q') && mods != KeyModifiers::CONTROL {
         if app.unsaved_changes {
             app.mode = AppMode::Confirm {
@@ -226,10 +500,8 @@ fn handle_key(
                 app.selected_monitor = app.monitors.len() - 1;
             }
         }
-        KeyCode::Down | KeyCode::Char('j') => {
-            if app.selected_monitor + 1 < app.monitors.len() {
-                app.selected_monitor += 1;
-            }
+        KeyCode::Down | KeyCode::Char('j') if app.selected_monitor + 1 < app.monitors.len() => {
+            app.selected_monitor += 1;
         }
         KeyCode::Tab => {
             app.focus_settings = !app.focus_settings;
@@ -243,73 +515,33 @@ fn handle_key(
             };
         }
         KeyCode::Char('m') => {
-            app.mode = AppMode::Moving;
+            app.begin_move();
         }
         KeyCode::Char(' ') => {
-            let idx = app.selected_monitor;
-            if idx < app.monitors.len() {
-                for mon in app.monitors.iter_mut() {
-                    mon.primary = false;
-                }
-                app.monitors[idx].primary = true;
-                app.unsaved_changes = true;
-            }
+            app.toggle_field(EditField::Primary);
         }
         KeyCode::Char('d') => {
-            if let Some(m) = app.selected_mut() {
-                m.enabled = !m.enabled;
-                app.unsaved_changes = true;
-            }
+            app.toggle_field(EditField::Enabled);
         }
         KeyCode::Char('r') => {
-            if let Some(m) = app.selected_mut() {
-                m.transform = m.transform.next();
-                app.unsaved_changes = true;
-            }
+            app.toggle_field(EditField::Transform);
         }
         KeyCode::Char('a') => {
-            app.push_history();
-            if let Err(e) = client.apply_all(&app.monitors) {
-                app.set_error(e.to_string());
-            } else {
-                app.set_status("Applied.".to_string());
-                app.unsaved_changes = false;
-            }
+            app.apply_live(backend);
         }
         KeyCode::Char('s') => {
-            let path = app
-                .config_path
-                .parent()
-                .unwrap_or_else(|| std::path::Path::new("."))
-                .join("profiles");
-            let _ = std::fs::create_dir_all(&path);
-            let profile = config::Profile::from_monitors(
-                "default".to_string(),
-                None,
-                &app.monitors,
-            );
-            let p = path.join("default.toml");
-            if let Err(e) = config::save_profile(&p, &profile) {
-                app.set_error(e.to_string());
-            } else {
-                app.set_status("Saved to default profile.".to_string());
-                app.unsaved_changes = false;
-            }
+            app.begin_save_profile();
         }
         KeyCode::Char('x') => {
-            let out = hyprland::generate_config(&app.monitors);
-            if let Err(e) = std::io::Write::write_all(&mut std::io::stdout(), out.as_bytes()) {
-                app.set_error(e.to_string());
-            } else {
-                app.set_status("Exported to stdout.".to_string());
-            }
+            app.persist_config();
         }
         KeyCode::Char('p') => {
-            app.mode = AppMode::ProfileSelect;
+            app.open_profiles();
         }
         KeyCode::Char('u') => {
             app.undo();
         }
+        KeyCode::Char('A') => app.begin_arrange(),
         KeyCode::Char('R') => {
             app.mode = AppMode::Confirm {
                 action: ConfirmAction::Reset,
@@ -324,25 +556,134 @@ fn handle_key(
     false
 }
 
-fn confirm_yes(app: &mut App, client: &hyprland::HyprlandClient, action: ConfirmAction) {
+fn handle_edit_key(app: &mut App, field: EditField, code: KeyCode) {
+    if app.picker.is_some() {
+        match code {
+            KeyCode::Up | KeyCode::Char('k') => app.picker_move(field, -1),
+            KeyCode::Down | KeyCode::Char('j') => app.picker_move(field, 1),
+            KeyCode::Enter | KeyCode::Char(' ') => app.picker_select(field),
+            KeyCode::Esc => app.picker = None,
+            _ => {}
+        }
+        return;
+    }
+    match code {
+        KeyCode::Tab | KeyCode::Down | KeyCode::Char('j') => {
+            app.mode = AppMode::Editing {
+                field: field.next(),
+            };
+        }
+        KeyCode::BackTab | KeyCode::Up | KeyCode::Char('k') => {
+            app.mode = AppMode::Editing {
+                field: field.prev(),
+            };
+        }
+        KeyCode::Enter | KeyCode::Char(' ') if field.has_picker() => app.open_picker(field),
+        KeyCode::Enter | KeyCode::Char(' ') if field == EditField::Reserved => {
+            app.begin_edit_reserved()
+        }
+        KeyCode::Enter | KeyCode::Char(' ') => app.toggle_field(field),
+        KeyCode::Left | KeyCode::Char('h') if field.is_slider() => app.step_field(field, -1.0),
+        KeyCode::Right | KeyCode::Char('l') if field.is_slider() => app.step_field(field, 1.0),
+        KeyCode::Esc | KeyCode::Char('q') => app.mode = AppMode::Normal,
+        _ => {}
+    }
+}
+
+fn handle_profile_key(app: &mut App, backend: &dyn MonitorBackend, code: KeyCode) {
+    match code {
+        KeyCode::Up | KeyCode::Char('k') => app.profile_move(-1),
+        KeyCode::Down | KeyCode::Char('j') => app.profile_move(1),
+        KeyCode::Enter | KeyCode::Char('l') => {
+            app.load_selected_profile();
+        }
+        KeyCode::Char('a') => app.apply_selected_profile(backend),
+        KeyCode::Char('r') => app.begin_rename_profile(),
+        KeyCode::Char('c') => app.duplicate_selected_profile(),
+        KeyCode::Char('d') => app.begin_delete_profile(),
+        KeyCode::Esc | KeyCode::Char('p') | KeyCode::Char('q') => app.mode = AppMode::Normal,
+        _ => {}
+    }
+}
+
+fn handle_prompt_key(app: &mut App, code: KeyCode) {
+    match code {
+        KeyCode::Enter => {
+            app.submit_prompt();
+            return;
+        }
+        KeyCode::Esc => {
+            app.close_prompt();
+            return;
+        }
+        _ => {}
+    }
+    let Some(prompt) = app.prompt.as_mut() else {
+        app.mode = AppMode::Normal;
+        return;
+    };
+    if matches!(code, KeyCode::Tab | KeyCode::BackTab) {
+        prompt.next_field();
+        return;
+    }
+    let Some(input) = prompt.input_mut() else {
+        return;
+    };
+    match code {
+        KeyCode::Char(c) => input.insert(c),
+        KeyCode::Backspace => input.backspace(),
+        KeyCode::Delete => input.delete(),
+        KeyCode::Left => input.left(),
+        KeyCode::Right => input.right(),
+        KeyCode::Home => input.home(),
+        KeyCode::End => input.end(),
+        _ => {}
+    }
+}
+
+fn handle_move_key(app: &mut App, code: KeyCode, mods: KeyModifiers) {
+    let small = app.settings.move_step;
+    let large = app.settings.move_step_large;
+    let step = if mods.contains(KeyModifiers::SHIFT) {
+        large
+    } else {
+        small
+    };
+    match code {
+        KeyCode::Left | KeyCode::Char('h') => app.nudge_selected(-step, 0),
+        KeyCode::Right | KeyCode::Char('l') => app.nudge_selected(step, 0),
+        KeyCode::Up | KeyCode::Char('k') => app.nudge_selected(0, -step),
+        KeyCode::Down | KeyCode::Char('j') => app.nudge_selected(0, step),
+        KeyCode::Char('H') => app.nudge_selected(-large, 0),
+        KeyCode::Char('L') => app.nudge_selected(large, 0),
+        KeyCode::Char('K') => app.nudge_selected(0, -large),
+        KeyCode::Char('J') => app.nudge_selected(0, large),
+        KeyCode::Enter => app.commit_move(),
+        KeyCode::Esc => app.cancel_move(),
+        _ => {}
+    }
+}
+
+fn confirm_yes(app: &mut App, backend: &dyn MonitorBackend, action: ConfirmAction) {
     match action {
         ConfirmAction::Quit => {
             std::process::exit(0);
         }
         ConfirmAction::Apply => {
-            let _ = client.apply_all(&app.monitors);
-            app.unsaved_changes = false;
+            app.apply_live(backend);
         }
         ConfirmAction::Save => {}
         ConfirmAction::Reset => {
-            if let Ok(monitors) = client.get_monitors() {
-                app.push_history();
-                app.monitors = monitors;
-                app.selected_monitor = app.selected_monitor.min(app.monitors.len().saturating_sub(1));
-                app.unsaved_changes = false;
+            app.reset_to_live(backend);
+        }
+        ConfirmAction::DeleteProfile(name) => {
+            app.delete_profile(&name);
+        }
+        ConfirmAction::OverwriteProfile { name, description } => {
+            if let Err(e) = app.save_profile_as(&name, description) {
+                app.set_error(format!("Failed to save profile: {}", e));
             }
         }
-        ConfirmAction::DeleteProfile(_) => {}
     }
 }
 
@@ -355,10 +696,34 @@ struct Cli {
     apply: Option<String>,
     #[arg(short, long)]
     export: bool,
+    /// With --export: select monitors by description (desc:...) instead of connector
+    #[arg(long, requires = "export")]
+    by_description: bool,
+    /// With --export: rule syntax, legacy (monitor=) or v2 (monitorv2 blocks)
+    #[arg(long, default_value = "legacy", requires = "export")]
+    syntax: hyprland::ConfigSyntax,
     #[arg(short = 'l', long)]
     list_profiles: bool,
     #[arg(short, long)]
     dry_run: bool,
+    /// With --apply: revert unless confirmed within preview_timeout seconds
+    #[arg(long, requires = "apply")]
+    confirm: bool,
+    /// Watch monitor hotplug and apply the best matching profile automatically
+    #[arg(long, conflicts_with_all = ["apply", "export", "list_profiles"])]
+    daemon: bool,
+    /// Daemon: quiet period after hotplug events before switching profiles
+    #[arg(long, default_value_t = 500, requires = "daemon")]
+    debounce_ms: u64,
+    /// Import monitor= rules from a Hyprland config (follows source = includes) as a profile
+    #[arg(long, value_name = "PATH", conflicts_with_all = ["apply", "export", "list_profiles", "daemon"])]
+    import: Option<PathBuf>,
+    /// With --import: name of the new profile [default: imported]
+    #[arg(long, requires = "import")]
+    name: Option<String>,
+    /// Check a profile's layout for overlaps, gaps and disconnected monitors
+    #[arg(long, value_name = "PROFILE", conflicts_with_all = ["apply", "export", "list_profiles", "daemon", "import"])]
+    validate: Option<String>,
     #[arg(short, long)]
     verbose: bool,
 }
@@ -1,6 +1,6 @@
 //! Confirmation dialogs and popups.
 
-use crate::app::ConfirmAction;
+use crate::app::{ConfirmAction, Prompt};
 use crate::config::Theme;
 use ratatui::layout::{Alignment, Rect};
 use ratatui::style::{Modifier, Style};
@@ -28,6 +28,7 @@ pub fn confirmation(
         ConfirmAction::Save => "Save to file",
         ConfirmAction::Reset => "Reset to current config",
         ConfirmAction::DeleteProfile(_) => "Delete profile",
+        ConfirmAction::OverwriteProfile { .. } => "Overwrite profile",
     };
     let text = vec![
         Line::from(Span::styled(
@@ -48,6 +49,95 @@ pub fn confirmation(
     f.render_widget(p, inner);
 }
 
+/// Draw the "keep these settings?" countdown shown after a live apply.
+pub fn keep_settings(f: &mut Frame, area: Rect, seconds_left: u64, theme: &Theme) {
+    let block = Block::default()
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(theme.warning))
+        .title(" Keep Settings? ");
+    let inner = block.inner(area);
+    f.render_widget(block, area);
+    let text = vec![
+        Line::from(Span::styled(
+            "Keep this monitor layout?",
+            Style::default()
+                .fg(theme.accent)
+                .add_modifier(Modifier::BOLD),
+        )),
+        Line::from(""),
+        Line::from(Span::styled(
+            format!("Reverting in {} s", seconds_left),
+            Style::default().fg(theme.warning),
+        )),
+        Line::from(""),
+        Line::from(Span::styled(
+            "[y] Keep  [n] Revert now",
+            Style::default().fg(theme.fg_dim),
+        )),
+    ];
+    let p = Paragraph::new(text)
+        .alignment(Alignment::Center)
+        .wrap(Wrap { trim: true });
+    f.render_widget(p, inner);
+}
+
+/// Draw a text prompt with one line per field and the cursor in the focused field.
+pub fn text_prompt(f: &mut Frame, area: Rect, prompt: &Prompt, theme: &Theme) {
+    let block = Block::default()
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(theme.accent))
+        .title(format!(" {} ", prompt.title));
+    let inner = block.inner(area);
+    f.render_widget(block, area);
+    let label_w = prompt
+        .fields
+        .iter()
+        .map(|(label, _)| label.chars().count())
+        .max()
+        .unwrap_or(0)
+        + 2;
+    let mut lines = Vec::new();
+    for (i, (label, input)) in prompt.fields.iter().enumerate() {
+        let style = if i == prompt.focused {
+            Style::default().fg(theme.accent)
+        } else {
+            Style::default().fg(theme.fg_dim)
+        };
+        lines.push(Line::from(vec![
+            Span::styled(
+                format!("{:width$}", format!("{}:", label), width = label_w),
+                style,
+            ),
+            Span::styled(input.value.clone(), Style::default().fg(theme.fg)),
+        ]));
+    }
+    lines.push(Line::from(""));
+    if let Some(err) = &prompt.error {
+        lines.push(Line::from(Span::styled(
+            err.clone(),
+            Style::default().fg(theme.error),
+        )));
+    }
+    let hint = if prompt.fields.len() > 1 {
+        "[Enter] OK  [Tab] Next field  [Esc] Cancel"
+    } else {
+        "[Enter] OK  [Esc] Cancel"
+    };
+    lines.push(Line::from(Span::styled(
+        hint,
+        Style::default().fg(theme.fg_dim),
+    )));
+    f.render_widget(Paragraph::new(lines), inner);
+
+    if let Some((_, input)) = prompt.fields.get(prompt.focused) {
+        let x = inner.x + (label_w + input.cursor) as u16;
+        let y = inner.y + prompt.focused as u16;
+        if x < inner.right() && y < inner.bottom() {
+            f.set_cursor_position((x, y));
+        }
+    }
+}
+
 /// Draw a simple message popup.
 #[allow(dead_code)]
 pub fn message(f: &mut Frame, area: Rect, title: &str, body: &str, theme: &Theme) {
@@ -17,9 +17,12 @@ const HELP_LINES: &[(&str, &str)] = &[
     ("d", "Enable/disable"),
     ("r", "Cycle rotation"),
     ("a", "Apply (live)"),
-    ("s", "Save to config"),
-    ("x", "Export snippet"),
+    ("s", "Save as profile"),
+    ("x", "Write Hyprland config"),
     ("p", "Profiles"),
+    ("A r / A c", "Arrange in a row (top / centred)"),
+    ("A v", "Arrange in a column"),
+    ("A l", "Arrange L-shape (laptop below)"),
     ("u", "Undo"),
     ("R", "Reset"),
     ("?", "This help"),
@@ -1,32 +1,69 @@
 //! Main TUI layout.
 
-use crate::app::{App, AppMode};
+use crate::app::{App, AppMode, ConfirmAction, EditField, PromptAction};
 use crate::config::Theme;
 use ratatui::layout::{Constraint, Direction, Layout, Rect};
 use ratatui::style::{Modifier, Style};
 use ratatui::text::{Line, Span};
-use ratatui::widgets::Paragraph;
+use ratatui::widgets::{Clear, Paragraph};
 use ratatui::Frame;
 
-use super::dialogs::confirmation;
+use super::dialogs::{confirmation, keep_settings, text_prompt};
 use super::help::help_overlay;
 use super::monitor_grid::monitor_grid;
 use super::preview::status_line;
+use super::profiles::profile_manager;
 use super::settings_panel::settings_panel;
+use super::widgets::option_list;
 
-/// Main layout: header, content (grid | settings), footer.
-pub fn draw(f: &mut Frame, app: &App, theme: &Theme) {
-    let area = f.area();
-    let chunks = Layout::default()
+/// Split the screen into header, content and footer rows.
+fn main_chunks(area: Rect) -> std::rc::Rc<[Rect]> {
+    Layout::default()
         .direction(Direction::Vertical)
         .constraints([
             Constraint::Length(1),
             Constraint::Min(10),
             Constraint::Length(1),
         ])
-        .split(area);
+        .split(area)
+}
+
+/// Split the content row into grid and settings panel.
+fn content_chunks(area: Rect) -> std::rc::Rc<[Rect]> {
+    Layout::default()
+        .direction(Direction::Horizontal)
+        .constraints([Constraint::Percentage(70), Constraint::Percentage(30)])
+        .split(area)
+}
+
+/// Area of the monitor grid block for a given screen size (for mouse hit-testing).
+pub fn grid_area(screen: Rect) -> Rect {
+    content_chunks(main_chunks(screen)[1])[0]
+}
+
+/// Whether the profile manager replaces the editor (including its own dialogs).
+fn on_profile_screen(app: &App) -> bool {
+    match &app.mode {
+        AppMode::ProfileSelect => true,
+        AppMode::Confirm { action, .. } => matches!(action, ConfirmAction::DeleteProfile(_)),
+        AppMode::Prompt => app
+            .prompt
+            .as_ref()
+            .is_some_and(|p| matches!(p.action, PromptAction::RenameProfile(_))),
+        _ => false,
+    }
+}
+
+/// Main layout: header, content (grid | settings), footer.
+pub fn draw(f: &mut Frame, app: &App, theme: &Theme) {
+    let area = f.area();
+    let chunks = main_chunks(area);
     draw_header(f, chunks[0], theme);
-    draw_content(f, chunks[1], app, theme);
+    if on_profile_screen(app) {
+        profile_manager(f, chunks[1], app, theme);
+    } else {
+        draw_content(f, chunks[1], app, theme);
+    }
     draw_footer(f, chunks[2], app, theme);
 
     if app.mode == AppMode::Help {
@@ -37,6 +74,23 @@ pub fn draw(f: &mut Frame, app: &App, theme: &Theme) {
         let dialog_area = centered_rect(50, 30, area);
         confirmation(f, dialog_area, action, message, theme);
     }
+    if let (AppMode::Prompt, Some(prompt)) = (&app.mode, &app.prompt) {
+        let width = area.width.min(60);
+        let height = (prompt.fields.len() as u16 + 5).min(area.height);
+        let dialog_area = Rect::new(
+            area.x + area.width.saturating_sub(width) / 2,
+            area.y + area.height.saturating_sub(height) / 2,
+            width,
+            height,
+        );
+        f.render_widget(Clear, dialog_area);
+        text_prompt(f, dialog_area, prompt, theme);
+    }
+    if let Some(secs) = app.revert_seconds_left() {
+        let dialog_area = centered_rect(50, 30, area);
+        f.render_widget(Clear, dialog_area);
+        keep_settings(f, dialog_area, secs, theme);
+    }
 }
 
 fn draw_header(f: &mut Frame, area: Rect, theme: &Theme) {
@@ -50,25 +104,31 @@ fn draw_header(f: &mut Frame, area: Rect, theme: &Theme) {
 }
 
 fn draw_content(f: &mut Frame, area: Rect, app: &App, theme: &Theme) {
-    let chunks = Layout::default()
-        .direction(Direction::Horizontal)
-        .constraints([Constraint::Percentage(70), Constraint::Percentage(30)])
-        .split(area);
+    let chunks = content_chunks(area);
     monitor_grid(
         f,
         chunks[0],
         &app.monitors,
         app.selected_monitor,
+        app.mode == AppMode::Moving,
         theme,
     );
     let monitor = app.selected();
-    settings_panel(
-        f,
-        chunks[1],
-        monitor,
-        theme,
-        None,
-    );
+    let edit_field = match app.mode {
+        AppMode::Editing { field } => Some(field),
+        _ => None,
+    };
+    settings_panel(f, chunks[1], monitor, theme, edit_field);
+    if let (Some(field), Some(selected)) = (edit_field, app.picker) {
+        let title = match field {
+            EditField::Resolution => " Resolution ",
+            EditField::Mirror => " Mirror Of ",
+            _ => " Refresh Rate ",
+        };
+        let items = app.picker_options(field);
+        f.render_widget(Clear, chunks[1]);
+        option_list(f, chunks[1], title, &items, Some(selected), theme);
+    }
 }
 
 fn draw_footer(f: &mut Frame, area: Rect, app: &App, theme: &Theme) {
@@ -77,10 +137,32 @@ fn draw_footer(f: &mut Frame, area: Rect, app: &App, theme: &Theme) {
     if status.is_some() || err.is_some() {
         status_line(f, area, status, err, theme);
     } else {
-        let line = Line::from(Span::styled(
-            "[↑↓] Select  [Enter] Edit  [m] Move  [a] Apply  [?] Help  [q] Quit",
-            Style::default().fg(theme.fg_dim),
-        ));
+        let moving_hint;
+        let hints = match app.mode {
+            AppMode::Editing { .. } if app.picker.is_some() => {
+                "[↑↓] Choose  [Enter] Select  [Esc] Cancel"
+            }
+            AppMode::Editing { .. } => {
+                "[Tab/↑↓] Field  [Enter] Pick/Toggle  [←→] Adjust scale  [Esc] Done"
+            }
+            AppMode::Moving => {
+                let pos = app
+                    .selected()
+                    .map(|m| format!("{} at {},{}  ", m.name, m.position.x, m.position.y))
+                    .unwrap_or_default();
+                moving_hint = format!(
+                    "{}[←↑↓→/hjkl] Nudge  [Shift] Large step  [Enter] Done  [Esc] Cancel",
+                    pos
+                );
+                moving_hint.as_str()
+            }
+            AppMode::ProfileSelect => {
+                "[↑↓] Select  [Enter] Load  [a] Apply  [r] Rename  [c] Duplicate  [d] Delete  [Esc] Back"
+            }
+            AppMode::Prompt => "[Enter] OK  [Esc] Cancel",
+            _ => "[↑↓] Select  [Enter] Edit  [m] Move  [a] Apply  [?] Help  [q] Quit",
+        };
+        let line = Line::from(Span::styled(hints, Style::default().fg(theme.fg_dim)));
         let p = Paragraph::new(line);
         f.render_widget(p, area);
     }
@@ -5,7 +5,9 @@ mod help;
 mod layout;
 mod monitor_grid;
 mod preview;
+mod profiles;
 mod settings_panel;
 mod widgets;
 
-pub use layout::draw;
+pub use layout::{draw, grid_area};
+pub use monitor_grid::{monitor_at, GridView};
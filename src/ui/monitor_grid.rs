@@ -1,7 +1,7 @@
 //! Visual monitor grid for placement.
 
 use crate::config::Theme;
-use crate::hyprland::Monitor;
+use crate::hyprland::{validate_layout, LayoutIssue, Monitor, Severity};
 use ratatui::layout::{Alignment, Rect};
 use ratatui::style::Style;
 use ratatui::text::Line;
@@ -12,18 +12,38 @@ use ratatui::Frame;
 const MIN_BOX_W: u16 = 12;
 const MIN_BOX_H: u16 = 4;
 
+/// Monitor whose picture `m` mirrors, if it is in the list.
+fn mirror_source<'a>(monitors: &'a [Monitor], m: &Monitor) -> Option<&'a Monitor> {
+    let source = m.mirror_of.as_deref()?;
+    monitors
+        .iter()
+        .find(|o| o.name == source && o.name != m.name)
+}
+
+/// Indices in drawing order: mirroring monitors last, stacked on top of their source
+/// so both title bars stay visible.
+fn draw_order(monitors: &[Monitor]) -> Vec<usize> {
+    let (mirrors, rest): (Vec<usize>, Vec<usize>) =
+        (0..monitors.len()).partition(|&i| mirror_source(monitors, &monitors[i]).is_some());
+    rest.into_iter().chain(mirrors).collect()
+}
+
 /// Scale factor and bounds for fitting all monitors in the grid area.
+/// Mirroring monitors are drawn over their source and don't count.
 fn scale_and_bounds(monitors: &[Monitor], inner: Rect) -> (f32, i32, i32, i32, i32) {
-    if monitors.is_empty() {
+    let placed: Vec<&Monitor> = monitors
+        .iter()
+        .filter(|m| mirror_source(monitors, m).is_none())
+        .collect();
+    if placed.is_empty() {
         return (1.0, 0, 0, 0, 0);
     }
-    let (min_x, max_x, min_y, max_y) = monitors.iter().fold(
+    let (min_x, max_x, min_y, max_y) = placed.iter().fold(
         (i32::MAX, i32::MIN, i32::MAX, i32::MIN),
         |(min_x, max_x, min_y, max_y), m| {
-            let w = m.resolution.width as i32;
-            let h = m.resolution.height as i32;
-            let x2 = m.position.x + w;
-            let y2 = m.position.y + h;
+            let (w, h) = m.logical_size();
+            let x2 = m.position.x + w as i32;
+            let y2 = m.position.y + h as i32;
             (
                 min_x.min(m.position.x),
                 max_x.max(x2),
@@ -42,18 +62,133 @@ fn scale_and_bounds(monitors: &[Monitor], inner: Rect) -> (f32, i32, i32, i32, i
     (scale, min_x, min_y, max_x, max_y)
 }
 
+/// Mapping between layout pixels and terminal cells for one frame of the grid.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct GridView {
+    /// Grid area inside the border
+    pub inner: Rect,
+    /// Cells per layout pixel
+    pub scale: f32,
+    /// Layout x shown at the left edge of `inner`
+    pub min_x: i32,
+    /// Layout y shown at the top edge of `inner`
+    pub min_y: i32,
+}
+
+impl GridView {
+    /// Fit `monitors` into the grid block drawn at `area`.
+    pub fn new(monitors: &[Monitor], area: Rect) -> Self {
+        let inner = grid_block(None).inner(area);
+        let (scale, min_x, min_y, _, _) = scale_and_bounds(monitors, inner);
+        Self {
+            inner,
+            scale,
+            min_x,
+            min_y,
+        }
+    }
+
+    /// Cell rectangle of a monitor box, clipped to the grid (None if fully clipped).
+    pub fn monitor_rect(&self, m: &Monitor) -> Option<Rect> {
+        let inner = self.inner;
+        let rel_x = (m.position.x - self.min_x) as f32 * self.scale;
+        let rel_y = (m.position.y - self.min_y) as f32 * self.scale;
+        let (lw, lh) = m.logical_size();
+        let w = (lw as f32 * self.scale).max(MIN_BOX_W as f32) as u16;
+        let h = (lh as f32 * self.scale).max(MIN_BOX_H as f32) as u16;
+        let x = inner.x + rel_x as u16;
+        let y = inner.y + rel_y as u16;
+        let box_w = w.min(inner.width.saturating_sub(rel_x as u16));
+        let box_h = h.min(inner.height.saturating_sub(rel_y as u16));
+        if box_w == 0 || box_h == 0 {
+            return None;
+        }
+        Some(Rect::new(x, y, box_w, box_h))
+    }
+
+    /// Cell rectangle of `monitors[index]`; a mirroring monitor is stacked one cell
+    /// below and right of its source.
+    pub fn box_rect(&self, monitors: &[Monitor], index: usize) -> Option<Rect> {
+        let m = monitors.get(index)?;
+        let Some(source) = mirror_source(monitors, m) else {
+            return self.monitor_rect(m);
+        };
+        let r = self.monitor_rect(source)?;
+        let x = (r.x + 1).min(self.inner.right().saturating_sub(1));
+        let y = (r.y + 1).min(self.inner.bottom().saturating_sub(1));
+        let w = r.width.min(self.inner.right().saturating_sub(x));
+        let h = r.height.min(self.inner.bottom().saturating_sub(y));
+        (w > 0 && h > 0).then(|| Rect::new(x, y, w, h))
+    }
+
+    /// Strips of `inner_box` covered by the monitor's reserved area, at least one cell
+    /// thick, leaving at least one row and column free.
+    pub fn reserved_strips(&self, m: &Monitor, inner_box: Rect) -> Vec<Rect> {
+        let Some(r) = m.reserved.filter(|r| !r.is_empty()) else {
+            return Vec::new();
+        };
+        let cells = |px: u32, max: u16| {
+            if px == 0 || max <= 1 {
+                0
+            } else {
+                ((px as f32 * self.scale).round() as u16).clamp(1, max / 2)
+            }
+        };
+        let (w, h) = (inner_box.width, inner_box.height);
+        let (top, bottom) = (cells(r.top, h), cells(r.bottom, h));
+        let (left, right) = (cells(r.left, w), cells(r.right, w));
+        let Rect { x, y, .. } = inner_box;
+        [
+            Rect::new(x, y, w, top),
+            Rect::new(x, y + h - bottom, w, bottom),
+            Rect::new(x, y + top, left, h - top - bottom),
+            Rect::new(x + w - right, y + top, right, h - top - bottom),
+        ]
+        .into_iter()
+        .filter(|s| s.width > 0 && s.height > 0)
+        .collect()
+    }
+
+    /// Layout pixel under a terminal cell (inverse of the grid scaling).
+    pub fn to_layout(&self, column: u16, row: u16) -> (i32, i32) {
+        let dx = (column as i32 - self.inner.x as i32) as f32 / self.scale;
+        let dy = (row as i32 - self.inner.y as i32) as f32 / self.scale;
+        (
+            self.min_x + dx.round() as i32,
+            self.min_y + dy.round() as i32,
+        )
+    }
+}
+
+/// Index of the monitor whose box covers the cell (topmost box wins).
+pub fn monitor_at(monitors: &[Monitor], view: &GridView, column: u16, row: u16) -> Option<usize> {
+    let cell = Rect::new(column, row, 1, 1);
+    draw_order(monitors).into_iter().rev().find(|&i| {
+        view.box_rect(monitors, i)
+            .is_some_and(|r| r.intersects(cell))
+    })
+}
+
+fn grid_block<'a>(theme: Option<&Theme>) -> Block<'a> {
+    let block = Block::default()
+        .borders(Borders::ALL)
+        .title(" Monitor Grid (Positions) ");
+    match theme {
+        Some(t) => block.border_style(Style::default().fg(t.border)),
+        None => block,
+    }
+}
+
 /// Draw the monitor grid with monitors at their relative positions.
 pub fn monitor_grid(
     f: &mut Frame,
     area: Rect,
     monitors: &[Monitor],
     selected: usize,
+    moving: bool,
     theme: &Theme,
 ) {
-    let block = Block::default()
-        .borders(Borders::ALL)
-        .border_style(Style::default().fg(theme.border))
-        .title(" Monitor Grid (Positions) ");
+    let block = grid_block(Some(theme));
     let inner = block.inner(area);
     f.render_widget(block, area);
 
@@ -64,30 +199,103 @@ pub fn monitor_grid(
         return;
     }
 
-    let (scale, min_x, min_y, _, _) = scale_and_bounds(monitors, inner);
+    let issues = validate_layout(monitors);
+    draw_monitors(
+        f,
+        &GridView::new(monitors, area),
+        monitors,
+        &issues,
+        selected,
+        moving,
+        theme,
+    );
+    draw_issues(f, inner, &issues, theme);
+}
 
-    for (i, m) in monitors.iter().enumerate() {
-        let rel_x = (m.position.x - min_x) as f32 * scale;
-        let rel_y = (m.position.y - min_y) as f32 * scale;
-        let w = (m.resolution.width as f32 * scale).max(MIN_BOX_W as f32) as u16;
-        let h = (m.resolution.height as f32 * scale).max(MIN_BOX_H as f32) as u16;
-        let x = inner.x + rel_x as u16;
-        let y = inner.y + rel_y as u16;
-        let box_w = w.min(inner.width.saturating_sub(rel_x as u16));
-        let box_h = h.min(inner.height.saturating_sub(rel_y as u16));
-        if box_w == 0 || box_h == 0 {
+fn severity_color(severity: Severity, theme: &Theme) -> ratatui::style::Color {
+    match severity {
+        Severity::Error => theme.error,
+        Severity::Warning => theme.warning,
+    }
+}
+
+/// List layout issues along the bottom of the grid, most severe first.
+fn draw_issues(f: &mut Frame, inner: Rect, issues: &[LayoutIssue], theme: &Theme) {
+    let rows = (issues.len() as u16).min(inner.height / 3);
+    if rows == 0 {
+        return;
+    }
+    let lines: Vec<Line> = issues
+        .iter()
+        .take(rows as usize)
+        .map(|i| {
+            let style = Style::default().fg(severity_color(i.severity, theme));
+            Line::styled(format!("⚠ {}", i), style)
+        })
+        .collect();
+    let area = Rect::new(inner.x, inner.bottom() - rows, inner.width, rows);
+    f.render_widget(Paragraph::new(lines), area);
+}
+
+/// Draw a read-only layout preview (no selection) in a titled block.
+pub fn layout_preview(f: &mut Frame, area: Rect, title: &str, monitors: &[Monitor], theme: &Theme) {
+    let block = Block::default()
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(theme.border))
+        .title(format!(" {} ", title));
+    let inner = block.inner(area);
+    f.render_widget(block, area);
+    if monitors.is_empty() {
+        let p = Paragraph::new("No connected monitor matches this profile")
+            .style(Style::default().fg(theme.fg_dim));
+        f.render_widget(p, inner);
+        return;
+    }
+    let issues = validate_layout(monitors);
+    draw_monitors(
+        f,
+        &GridView::new(monitors, area),
+        monitors,
+        &issues,
+        usize::MAX,
+        false,
+        theme,
+    );
+}
+
+/// Draw monitor boxes at their grid positions.
+fn draw_monitors(
+    f: &mut Frame,
+    view: &GridView,
+    monitors: &[Monitor],
+    issues: &[LayoutIssue],
+    selected: usize,
+    moving: bool,
+    theme: &Theme,
+) {
+    for i in draw_order(monitors) {
+        let m = &monitors[i];
+        let Some(box_area) = view.box_rect(monitors, i) else {
             continue;
-        }
-        let box_area = Rect::new(x, y, box_w, box_h);
-        let border_style = if i == selected {
+        };
+        let border_style = if i == selected && moving {
+            Style::default().fg(theme.accent_secondary)
+        } else if i == selected {
             Style::default().fg(theme.border_active)
+        } else if let Some(severity) = issues
+            .iter()
+            .filter(|issue| issue.monitors.contains(&m.name))
+            .map(|issue| issue.severity)
+            .max()
+        {
+            Style::default().fg(severity_color(severity, theme))
         } else {
             Style::default().fg(theme.border)
         };
-        let title = if m.primary {
-            format!(" {} ★", m.name)
-        } else {
-            format!(" {} ", m.name)
+        let title = match (&m.mirror_of, m.primary) {
+            (Some(source), _) => format!(" {} ⇐ {} ", m.name, source),
+            (None, true) => format!(" {} ★", m.name),
+            (None, false) => format!(" {} ", m.name),
         };
         let b = Block::default()
             .borders(Borders::ALL)
@@ -96,10 +304,36 @@ pub fn monitor_grid(
             .style(Style::default().bg(theme.selection));
         let inner_box = b.inner(box_area);
         f.render_widget(b, box_area);
-        let lines = vec![
+        for strip in view.reserved_strips(m, inner_box) {
+            f.render_widget(
+                Block::default().style(Style::default().bg(theme.border)),
+                strip,
+            );
+        }
+        let mut lines = vec![
             Line::from(format!("{}", m.resolution)),
             Line::from(format!("{} Hz", m.refresh_rate as u32)),
         ];
+        // Values Hyprland resolves itself are shown resolved, with the symbolic name below.
+        let symbolic: Vec<String> = [
+            m.mode_spec
+                .as_ref()
+                .filter(|s| s.is_symbolic())
+                .map(|s| s.to_string()),
+            m.position_spec
+                .as_ref()
+                .filter(|s| s.is_symbolic())
+                .map(|s| s.to_string()),
+        ]
+        .into_iter()
+        .flatten()
+        .collect();
+        if !symbolic.is_empty() {
+            lines.push(Line::styled(
+                symbolic.join(" "),
+                Style::default().fg(theme.fg_dim),
+            ));
+        }
         let p = Paragraph::new(lines)
             .alignment(Alignment::Center)
             .style(Style::default().fg(theme.fg));
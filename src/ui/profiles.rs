@@ -0,0 +1,124 @@
+//! Profile manager screen.
+
+use crate::app::App;
+use crate::config::Theme;
+use ratatui::layout::{Constraint, Direction, Layout, Rect};
+use ratatui::style::{Modifier, Style};
+use ratatui::text::{Line, Span};
+use ratatui::widgets::{Block, Borders, List, ListItem, ListState, Paragraph, Wrap};
+use ratatui::Frame;
+
+use super::monitor_grid::layout_preview;
+
+/// Format an RFC 3339 timestamp for display (falls back to the raw string).
+fn format_time(ts: Option<&str>) -> String {
+    match ts {
+        Some(s) => chrono::DateTime::parse_from_rfc3339(s)
+            .map(|t| {
+                t.with_timezone(&chrono::Local)
+                    .format("%Y-%m-%d %H:%M")
+                    .to_string()
+            })
+            .unwrap_or_else(|_| s.to_string()),
+        None => "-".to_string(),
+    }
+}
+
+/// Draw the profile list with a layout preview of the highlighted profile.
+pub fn profile_manager(f: &mut Frame, area: Rect, app: &App, theme: &Theme) {
+    let chunks = Layout::default()
+        .direction(Direction::Horizontal)
+        .constraints([Constraint::Percentage(40), Constraint::Percentage(60)])
+        .split(area);
+
+    let block = Block::default()
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(theme.border_active))
+        .title(" Profiles ");
+    if app.profiles.is_empty() {
+        let inner = block.inner(chunks[0]);
+        f.render_widget(block, chunks[0]);
+        let p = Paragraph::new("No saved profiles. Press [s] in the editor to save one.")
+            .style(Style::default().fg(theme.fg_dim))
+            .wrap(Wrap { trim: true });
+        f.render_widget(p, inner);
+    } else {
+        let items: Vec<ListItem> = app
+            .profiles
+            .iter()
+            .map(|e| {
+                let mut title = vec![Span::styled(
+                    e.file_name.clone(),
+                    Style::default().fg(theme.fg).add_modifier(Modifier::BOLD),
+                )];
+                if let Some(desc) = e.profile.description.as_deref().filter(|d| !d.is_empty()) {
+                    title.push(Span::styled(
+                        format!("  {}", desc),
+                        Style::default().fg(theme.fg_dim),
+                    ));
+                }
+                ListItem::new(vec![
+                    Line::from(title),
+                    Line::from(Span::styled(
+                        format!(
+                            "  modified {}",
+                            format_time(e.profile.modified_at.as_deref())
+                        ),
+                        Style::default().fg(theme.fg_dim),
+                    )),
+                ])
+            })
+            .collect();
+        let list = List::new(items)
+            .block(block)
+            .highlight_style(Style::default().bg(theme.selection).fg(theme.accent))
+            .highlight_symbol("▸ ");
+        let mut state = ListState::default().with_selected(Some(app.selected_profile));
+        f.render_stateful_widget(list, chunks[0], &mut state);
+    }
+
+    let right = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([Constraint::Min(6), Constraint::Length(6)])
+        .split(chunks[1]);
+    let Some(entry) = app.selected_profile_entry() else {
+        layout_preview(f, right[0], "Preview", &[], theme);
+        return;
+    };
+    layout_preview(f, right[0], "Preview", &app.profile_preview(), theme);
+
+    let matched = entry.profile.match_monitors(&app.monitors);
+    let mut lines = vec![
+        Line::from(format!(
+            "Created {}   Modified {}",
+            format_time(entry.profile.created_at.as_deref()),
+            format_time(entry.profile.modified_at.as_deref())
+        )),
+        Line::from(format!(
+            "Outputs: {}",
+            entry
+                .profile
+                .monitors
+                .iter()
+                .map(|m| m.name.as_str())
+                .collect::<Vec<_>>()
+                .join(", ")
+        )),
+    ];
+    if !matched.unmatched.is_empty() {
+        lines.push(Line::from(Span::styled(
+            format!("Not connected: {}", matched.unmatched.join(", ")),
+            Style::default().fg(theme.warning),
+        )));
+    }
+    let details = Paragraph::new(lines)
+        .block(
+            Block::default()
+                .borders(Borders::ALL)
+                .border_style(Style::default().fg(theme.border))
+                .title(format!(" {} ", entry.profile.name)),
+        )
+        .style(Style::default().fg(theme.fg))
+        .wrap(Wrap { trim: true });
+    f.render_widget(details, right[1]);
+}
@@ -1,14 +1,34 @@
 //! Settings panel (resolution, Hz, scale).
 
+use crate::app::EditField;
 use crate::config::Theme;
-use crate::hyprland::Monitor;
+use crate::hyprland::{is_valid_scale, Monitor, SDR_BRIGHTNESS_RANGE, SDR_SATURATION_RANGE};
 use ratatui::layout::{Constraint, Layout, Rect};
-use ratatui::style::Style;
+use ratatui::style::{Modifier, Style};
 use ratatui::text::{Line, Span};
 use ratatui::widgets::{Block, Borders, Paragraph};
 use ratatui::Frame;
 
-use super::widgets::scale_slider;
+use super::widgets::{range_slider, scale_slider};
+
+/// Field label, highlighted when it is the field being edited.
+fn label<'a>(
+    text: &'a str,
+    field: EditField,
+    edit_field: Option<EditField>,
+    theme: &Theme,
+) -> Span<'a> {
+    if edit_field == Some(field) {
+        Span::styled(
+            format!("▸ {}", text),
+            Style::default()
+                .fg(theme.accent)
+                .add_modifier(Modifier::BOLD),
+        )
+    } else {
+        Span::styled(text, Style::default().fg(theme.fg_dim))
+    }
+}
 
 /// Draw the settings panel for the selected monitor.
 pub fn settings_panel(
@@ -16,11 +36,16 @@ pub fn settings_panel(
     area: Rect,
     monitor: Option<&Monitor>,
     theme: &Theme,
-    _edit_field: Option<crate::app::EditField>,
+    edit_field: Option<EditField>,
 ) {
+    let border = if edit_field.is_some() {
+        theme.border_active
+    } else {
+        theme.border
+    };
     let block = Block::default()
         .borders(Borders::ALL)
-        .border_style(Style::default().fg(theme.border))
+        .border_style(Style::default().fg(border))
         .title(" Settings ");
     let inner = block.inner(area);
     f.render_widget(block, area);
@@ -37,47 +62,165 @@ pub fn settings_panel(
             Constraint::Length(3),
             Constraint::Length(2),
             Constraint::Length(2),
+            Constraint::Length(2),
+            Constraint::Length(2),
+            Constraint::Length(1),
+            Constraint::Length(1),
+            Constraint::Length(1),
+            Constraint::Length(1),
+            Constraint::Length(2),
             Constraint::Min(0),
         ])
         .split(inner);
     // Preview: resolution with refresh rate below
-    let preview_lines = vec![
+    let mut preview_lines = vec![
         Line::from(vec![
-            Span::styled("Resolution ", Style::default().fg(theme.fg_dim)),
+            label("Resolution ", EditField::Resolution, edit_field, theme),
             Span::styled(m.resolution.to_string(), Style::default().fg(theme.fg)),
+            Span::styled(
+                m.mode_spec
+                    .as_ref()
+                    .filter(|s| s.is_symbolic())
+                    .map(|s| format!(" ({})", s))
+                    .unwrap_or_default(),
+                Style::default().fg(theme.fg_dim),
+            ),
         ]),
         Line::from(vec![
-            Span::styled("Refresh Rate ", Style::default().fg(theme.fg_dim)),
+            label("Refresh Rate ", EditField::RefreshRate, edit_field, theme),
             Span::styled(
-                format!("{:.0} Hz", m.refresh_rate),
+                format!("{:.2} Hz", m.refresh_rate),
                 Style::default().fg(theme.fg),
             ),
         ]),
     ];
+    let (lw, lh) = m.logical_size();
+    if (lw, lh) != (m.resolution.width, m.resolution.height) {
+        preview_lines.push(Line::from(Span::styled(
+            format!("Logical {}x{}", lw, lh),
+            Style::default().fg(theme.fg_dim),
+        )));
+    }
     let preview = Paragraph::new(preview_lines);
     f.render_widget(preview, chunks[0]);
-    let scale_line = Line::from(vec![
-        Span::styled("Scale ", Style::default().fg(theme.fg_dim)),
+    let mut scale_line = Line::from(vec![
+        label("Scale ", EditField::Scale, edit_field, theme),
         Span::styled(format!("{:.2}", m.scale), Style::default().fg(theme.fg)),
     ]);
+    if !is_valid_scale(&m.resolution, m.scale) {
+        scale_line.push_span(Span::styled(
+            " (not valid for this resolution)",
+            Style::default().fg(theme.warning),
+        ));
+    }
     f.render_widget(Paragraph::new(scale_line), chunks[1]);
-    scale_slider(f, chunks[2], m.scale, false, theme);
+    scale_slider(
+        f,
+        chunks[2],
+        m.scale,
+        edit_field == Some(EditField::Scale),
+        theme,
+    );
     let transform_line = Line::from(vec![
-        Span::styled("Transform ", Style::default().fg(theme.fg_dim)),
+        label("Transform ", EditField::Transform, edit_field, theme),
         Span::styled(
             format!("{}", m.transform),
             Style::default().fg(theme.fg),
         ),
     ]);
     f.render_widget(Paragraph::new(transform_line), chunks[3]);
+    let mirror_line = Line::from(vec![
+        label("Mirror Of ", EditField::Mirror, edit_field, theme),
+        Span::styled(
+            m.mirror_of.as_deref().unwrap_or("None").to_string(),
+            Style::default().fg(theme.fg),
+        ),
+    ]);
+    let reserved = match m.reserved.filter(|r| !r.is_empty()) {
+        Some(r) => format!("T{} B{} L{} R{}", r.top, r.bottom, r.left, r.right),
+        None => "None".to_string(),
+    };
+    let reserved_line = Line::from(vec![
+        label("Reserved ", EditField::Reserved, edit_field, theme),
+        Span::styled(reserved, Style::default().fg(theme.fg)),
+    ]);
+    f.render_widget(Paragraph::new(vec![mirror_line, reserved_line]), chunks[4]);
+    let vrr = m
+        .vrr
+        .map(|v| v.to_string())
+        .unwrap_or_else(|| "Default".to_string());
+    let display_line = Line::from(vec![
+        label("VRR: ", EditField::Vrr, edit_field, theme),
+        Span::styled(vrr, Style::default().fg(theme.fg)),
+        Span::raw("  "),
+        label("Bit depth: ", EditField::Bitdepth, edit_field, theme),
+        Span::styled(m.bitdepth.to_string(), Style::default().fg(theme.fg)),
+    ]);
+    f.render_widget(Paragraph::new(display_line), chunks[5]);
+    colour_section(f, &chunks[6..11], m, theme, edit_field);
     let enabled = if m.enabled { "Yes" } else { "No" };
     let primary = if m.primary { "Yes" } else { "No" };
     let opts_line = Line::from(vec![
-        Span::styled("Enabled: ", Style::default().fg(theme.fg_dim)),
+        label("Enabled: ", EditField::Enabled, edit_field, theme),
         Span::styled(enabled, Style::default().fg(theme.fg)),
         Span::raw("  "),
-        Span::styled("Primary: ", Style::default().fg(theme.fg_dim)),
+        label("Primary: ", EditField::Primary, edit_field, theme),
         Span::styled(primary, Style::default().fg(theme.fg)),
     ]);
-    f.render_widget(Paragraph::new(opts_line), chunks[4]);
+    f.render_widget(Paragraph::new(opts_line), chunks[11]);
+}
+
+/// "Colour" section: preset, then SDR brightness and saturation sliders.
+fn colour_section(
+    f: &mut Frame,
+    chunks: &[Rect],
+    m: &Monitor,
+    theme: &Theme,
+    edit_field: Option<EditField>,
+) {
+    let preset = m
+        .color_mode
+        .map(|cm| cm.to_string())
+        .unwrap_or_else(|| "Default".to_string());
+    let lines = vec![
+        Line::from(Span::styled(
+            "Colour",
+            Style::default()
+                .fg(theme.accent_secondary)
+                .add_modifier(Modifier::BOLD),
+        )),
+        Line::from(vec![
+            label("Preset ", EditField::ColorMode, edit_field, theme),
+            Span::styled(preset, Style::default().fg(theme.fg)),
+        ]),
+    ];
+    f.render_widget(Paragraph::new(lines), chunks[0]);
+    // SDR values only take effect in HDR presets.
+    let hdr = m.color_mode.is_some_and(|cm| cm.is_hdr());
+    let sliders = [
+        (
+            "SDR Brightness",
+            EditField::SdrBrightness,
+            m.sdr_brightness,
+            SDR_BRIGHTNESS_RANGE,
+        ),
+        (
+            "SDR Saturation",
+            EditField::SdrSaturation,
+            m.sdr_saturation,
+            SDR_SATURATION_RANGE,
+        ),
+    ];
+    for (i, (text, field, value, range)) in sliders.into_iter().enumerate() {
+        let mut line = vec![label(text, field, edit_field, theme)];
+        if !hdr {
+            line.push(Span::styled(
+                " (HDR only)",
+                Style::default().fg(theme.fg_dim),
+            ));
+        }
+        f.render_widget(Paragraph::new(Line::from(line)), chunks[1 + i * 2]);
+        let selected = edit_field == Some(field);
+        range_slider(f, chunks[2 + i * 2], value, 1.0, range, selected, theme);
+    }
 }
@@ -4,7 +4,7 @@ use crate::config::Theme;
 use ratatui::layout::Rect;
 use ratatui::style::{Modifier, Style};
 use ratatui::text::{Line, Span};
-use ratatui::widgets::{Block, Borders, Gauge, List, ListItem, Paragraph, Wrap};
+use ratatui::widgets::{Block, Borders, Gauge, List, ListItem, ListState, Paragraph, Wrap};
 use ratatui::Frame;
 
 /// Draw a monitor box (name, resolution, Hz, primary star).
@@ -61,8 +61,38 @@ pub fn scale_slider(
     f.render_widget(gauge, area);
 }
 
-/// Draw a list of options (e.g. resolutions).
-#[allow(dead_code)]
+/// Draw a slider for `value` within `range`; `None` is drawn dimmed at `default`.
+pub fn range_slider(
+    f: &mut Frame,
+    area: Rect,
+    value: Option<f32>,
+    default: f32,
+    range: std::ops::RangeInclusive<f32>,
+    selected: bool,
+    theme: &Theme,
+) {
+    let (start, end) = (*range.start(), *range.end());
+    let v = value.unwrap_or(default);
+    let ratio = ((v - start) / (end - start)).clamp(0.0, 1.0);
+    let style = if selected {
+        Style::default().fg(theme.accent)
+    } else if value.is_none() {
+        Style::default().fg(theme.fg_dim)
+    } else {
+        Style::default().fg(theme.border)
+    };
+    let label = match value {
+        Some(v) => format!("{:.2}", v),
+        None => "Default".to_string(),
+    };
+    let gauge = Gauge::default()
+        .gauge_style(style)
+        .ratio(ratio as f64)
+        .label(label);
+    f.render_widget(gauge, area);
+}
+
+/// Draw a list of options (e.g. resolutions), scrolled to keep the selection visible.
 pub fn option_list(
     f: &mut Frame,
     area: Rect,
@@ -83,13 +113,16 @@ pub fn option_list(
             ListItem::new(s.as_str()).style(style)
         })
         .collect();
-    let list = List::new(list_items).block(
-        Block::default()
-            .borders(Borders::ALL)
-            .border_style(Style::default().fg(theme.border))
-            .title(title),
-    );
-    f.render_widget(list, area);
+    let list = List::new(list_items)
+        .block(
+            Block::default()
+                .borders(Borders::ALL)
+                .border_style(Style::default().fg(theme.border_active))
+                .title(title),
+        )
+        .highlight_symbol("▸ ");
+    let mut state = ListState::default().with_selected(selected_index);
+    f.render_stateful_widget(list, area, &mut state);
 }
 
 /// Draw a labeled value row.
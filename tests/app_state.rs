@@ -0,0 +1,548 @@
+//! App state tests (merging, history).
+
+mod common;
+
+use hypr_monitor_tui::app::{App, AppMode, ConfirmAction, EditField, TextInput};
+use hypr_monitor_tui::config::{self, Profile, Theme};
+use hypr_monitor_tui::hyprland::{
+    Arrangement, ColorMode, Mode, Monitor, Position, Reserved, Resolution, Vrr,
+};
+use std::path::PathBuf;
+
+fn make_monitor(name: &str, x: i32, w: u32, h: u32) -> Monitor {
+    let mut m = common::monitor(name, x, 0, w, h);
+    m.primary = name == "eDP-1";
+    m
+}
+
+fn make_app(monitors: Vec<Monitor>) -> App {
+    App::new(
+        monitors,
+        PathBuf::from("/nonexistent/config.toml"),
+        Theme::default(),
+    )
+}
+
+/// App whose config.toml lives in `dir`, with the given profiles saved beside it.
+fn make_app_with_profiles(
+    dir: &std::path::Path,
+    monitors: Vec<Monitor>,
+    profiles: &[Profile],
+) -> App {
+    let profiles_dir = dir.join("profiles");
+    std::fs::create_dir_all(&profiles_dir).unwrap();
+    for p in profiles {
+        config::save_profile(&profiles_dir.join(format!("{}.toml", p.name)), p).unwrap();
+    }
+    App::new(monitors, dir.join("config.toml"), Theme::default())
+}
+
+#[test]
+fn test_merge_keeps_pending_edits_for_connected_monitors() {
+    let mut app = make_app(vec![
+        make_monitor("eDP-1", 0, 1920, 1080),
+        make_monitor("DP-1", 1920, 2560, 1440),
+    ]);
+    app.monitors[1].scale = 1.5;
+    app.unsaved_changes = true;
+    app.selected_monitor = 1;
+
+    app.merge_monitors(vec![
+        make_monitor("DP-1", 1920, 2560, 1440),
+        make_monitor("HDMI-A-1", 4480, 1920, 1080),
+    ]);
+
+    let names: Vec<_> = app.monitors.iter().map(|m| m.name.as_str()).collect();
+    assert_eq!(names, ["DP-1", "HDMI-A-1"]);
+    assert!((app.monitors[0].scale - 1.5).abs() < f32::EPSILON);
+    assert_eq!(app.selected_monitor, 0);
+}
+
+#[test]
+fn test_undo_after_unplug_does_not_restore_disconnected_monitor() {
+    let mut app = make_app(vec![
+        make_monitor("eDP-1", 0, 1920, 1080),
+        make_monitor("DP-1", 1920, 2560, 1440),
+    ]);
+    app.selected_monitor = 1;
+    app.toggle_field(EditField::Transform);
+
+    app.merge_monitors(vec![make_monitor("eDP-1", 0, 1920, 1080)]);
+    assert!(!app.undo());
+    let names: Vec<_> = app.monitors.iter().map(|m| m.name.as_str()).collect();
+    assert_eq!(names, ["eDP-1"]);
+
+    // A refresh with the same outputs keeps the history.
+    app.toggle_field(EditField::Transform);
+    app.merge_monitors(vec![make_monitor("eDP-1", 0, 1920, 1080)]);
+    assert!(app.undo());
+}
+
+#[test]
+fn test_merge_takes_live_state_without_pending_edits() {
+    let mut app = make_app(vec![make_monitor("DP-1", 0, 2560, 1440)]);
+    app.merge_monitors(vec![make_monitor("DP-1", 100, 2560, 1440)]);
+    assert_eq!(app.monitors[0].position.x, 100);
+}
+
+#[test]
+fn test_picker_resolution_adjusts_refresh_rate() {
+    let mut m = make_monitor("DP-1", 0, 2560, 1440);
+    m.refresh_rate = 144.0;
+    m.available_modes = vec![
+        Mode {
+            width: 2560,
+            height: 1440,
+            refresh_rate: 144.0,
+        },
+        Mode {
+            width: 1920,
+            height: 1080,
+            refresh_rate: 120.0,
+        },
+        Mode {
+            width: 1920,
+            height: 1080,
+            refresh_rate: 60.0,
+        },
+    ];
+    m.available_resolutions = vec![
+        Resolution {
+            width: 2560,
+            height: 1440,
+        },
+        Resolution {
+            width: 1920,
+            height: 1080,
+        },
+    ];
+    let mut app = make_app(vec![m]);
+
+    app.open_picker(EditField::Resolution);
+    assert_eq!(app.picker, Some(0));
+    app.picker_move(EditField::Resolution, 1);
+    app.picker_select(EditField::Resolution);
+
+    assert_eq!(app.picker, None);
+    assert_eq!(app.monitors[0].resolution.to_string(), "1920x1080");
+    assert!((app.monitors[0].refresh_rate - 120.0).abs() < 0.01);
+    assert_eq!(
+        app.picker_options(EditField::RefreshRate),
+        ["120.00 Hz", "60.00 Hz"]
+    );
+    assert_eq!(app.history.len(), 1);
+    assert!(app.unsaved_changes);
+}
+
+#[test]
+fn test_step_scale_uses_configured_step() {
+    let mut app = make_app(vec![make_monitor("DP-1", 0, 1920, 1080)]);
+    app.settings.scale_step = 0.25;
+    app.step_scale(1.0);
+    app.step_scale(1.0);
+    assert!((app.monitors[0].scale - 1.5).abs() < f32::EPSILON);
+    assert_eq!(app.history.len(), 2);
+    assert!(app.undo());
+    assert!((app.monitors[0].scale - 1.25).abs() < f32::EPSILON);
+}
+
+#[test]
+fn test_step_scale_skips_scales_hyprland_rejects() {
+    // 2560 / 1.5 isn't a whole pixel; the nearest valid scale is 1.6 (2560x1440 -> 1600x900).
+    let mut app = make_app(vec![make_monitor("DP-1", 0, 2560, 1440)]);
+    app.settings.scale_step = 0.25;
+    app.step_scale(1.0);
+    app.step_scale(1.0);
+    assert_eq!(app.monitors[0].scale, 1.6);
+    app.step_scale(-1.0);
+    assert!((app.monitors[0].scale - 4.0 / 3.0).abs() < 1e-6);
+
+    // An invalid scale from a profile still moves in the requested direction.
+    app.monitors[0].scale = 1.33;
+    app.step_scale(1.0);
+    assert_eq!(app.monitors[0].scale, 1.6);
+}
+
+#[test]
+fn test_move_nudge_snaps_and_cancel_restores() {
+    let mut app = make_app(vec![
+        make_monitor("eDP-1", 0, 1920, 1080),
+        make_monitor("DP-1", 1925, 2560, 1440),
+    ]);
+    app.selected_monitor = 1;
+    app.begin_move();
+    assert_eq!(app.mode, AppMode::Moving);
+    app.nudge_selected(-10, 0);
+    assert_eq!(app.monitors[1].position.x, 1920);
+    app.cancel_move();
+    assert_eq!(app.mode, AppMode::Normal);
+    assert_eq!(app.monitors[1].position.x, 1925);
+    assert!(app.history.is_empty());
+    assert!(!app.unsaved_changes);
+}
+
+#[test]
+fn test_move_commit_keeps_single_undo_step() {
+    let mut app = make_app(vec![make_monitor("DP-1", 0, 2560, 1440)]);
+    app.begin_move();
+    app.nudge_selected(100, 0);
+    app.nudge_selected(0, 10);
+    app.commit_move();
+    assert_eq!(app.monitors[0].position, Position { x: 100, y: 10 });
+    assert_eq!(app.history.len(), 1);
+    assert!(app.undo());
+    assert_eq!(app.monitors[0].position, Position { x: 0, y: 0 });
+}
+
+#[test]
+fn test_unconfirmed_apply_reverts_to_previous_layout() {
+    let live = vec![make_monitor("DP-1", 0, 2560, 1440)];
+    let mut app = make_app(live.clone());
+    app.settings.preview_timeout = 0;
+    app.monitors[0].scale = 2.0;
+    app.start_revert_countdown(live);
+
+    assert!(app.revert_due(std::time::Instant::now()));
+    let previous = app.revert_applied().unwrap();
+    assert!((previous[0].scale - 1.0).abs() < f32::EPSILON);
+    assert!((app.monitors[0].scale - 1.0).abs() < f32::EPSILON);
+    assert_eq!(app.mode, AppMode::Normal);
+
+    // The rejected edit is still reachable through undo.
+    assert!(app.undo());
+    assert!((app.monitors[0].scale - 2.0).abs() < f32::EPSILON);
+}
+
+#[test]
+fn test_keep_applied_clears_countdown() {
+    let mut app = make_app(vec![make_monitor("DP-1", 0, 2560, 1440)]);
+    app.unsaved_changes = true;
+    app.start_revert_countdown(app.monitors.clone());
+    assert!(app.revert_seconds_left().is_some());
+    app.keep_applied();
+    assert_eq!(app.revert_seconds_left(), None);
+    // Applied is not saved: quitting still has to warn.
+    assert!(app.unsaved_changes);
+    assert!(app.revert_applied().is_none());
+}
+
+#[test]
+fn test_profile_manager_loads_profile_into_editor() {
+    let dir = tempfile::tempdir().unwrap();
+    let mut docked = make_monitor("DP-1", 0, 2560, 1440);
+    docked.scale = 1.25;
+    let profile = Profile::from_monitors("desk".to_string(), None, &[docked]);
+    let mut app = make_app_with_profiles(
+        dir.path(),
+        vec![
+            make_monitor("eDP-1", 0, 1920, 1080),
+            make_monitor("DP-1", 1920, 2560, 1440),
+        ],
+        &[profile],
+    );
+    app.open_profiles();
+    assert_eq!(app.profiles.len(), 1);
+    assert_eq!(app.profile_preview().len(), 1);
+
+    assert!(app.load_selected_profile());
+    assert_eq!(app.mode, AppMode::Normal);
+    assert!(app.unsaved_changes);
+    assert_eq!(app.monitors[0].name, "eDP-1");
+    assert_eq!(app.monitors[1].position.x, 0);
+    assert!((app.monitors[1].scale - 1.25).abs() < f32::EPSILON);
+    assert!(app.undo());
+    assert_eq!(app.monitors[1].position.x, 1920);
+}
+
+#[test]
+fn test_profile_manager_rename_duplicate_delete() {
+    let dir = tempfile::tempdir().unwrap();
+    let monitors = vec![make_monitor("eDP-1", 0, 1920, 1080)];
+    let profile = Profile::from_monitors("laptop".to_string(), None, &monitors);
+    let mut app = make_app_with_profiles(dir.path(), monitors, &[profile]);
+    let profiles_dir = dir.path().join("profiles");
+
+    app.duplicate_selected_profile();
+    assert_eq!(app.profiles[app.selected_profile].file_name, "laptop-copy");
+    app.selected_profile = 0;
+    app.duplicate_selected_profile();
+    assert!(profiles_dir.join("laptop-copy-2.toml").exists());
+
+    assert!(app.rename_profile("laptop-copy", "laptop").is_err());
+    assert!(app.rename_profile("laptop-copy", "bad/name").is_err());
+    app.rename_profile("laptop-copy", "travel").unwrap();
+    assert!(!profiles_dir.join("laptop-copy.toml").exists());
+    let renamed = config::load_profile(&profiles_dir.join("travel.toml")).unwrap();
+    assert_eq!(renamed.name, "travel");
+    assert_eq!(app.profiles[app.selected_profile].file_name, "travel");
+
+    app.begin_delete_profile();
+    assert!(matches!(app.mode, AppMode::Confirm { .. }));
+    app.delete_profile("travel");
+    let names: Vec<_> = app.profiles.iter().map(|e| e.file_name.as_str()).collect();
+    assert_eq!(names, ["laptop", "laptop-copy-2"]);
+}
+
+#[test]
+fn test_rename_prompt_stays_open_on_invalid_name() {
+    let dir = tempfile::tempdir().unwrap();
+    let monitors = vec![make_monitor("eDP-1", 0, 1920, 1080)];
+    let profile = Profile::from_monitors("laptop".to_string(), None, &monitors);
+    let mut app = make_app_with_profiles(dir.path(), monitors, &[profile]);
+    app.open_profiles();
+    app.begin_rename_profile();
+    assert_eq!(app.mode, AppMode::Prompt);
+
+    let input = app.prompt.as_mut().unwrap().input_mut().unwrap();
+    input.end();
+    input.insert('/');
+    app.submit_prompt();
+    assert_eq!(app.mode, AppMode::Prompt);
+    assert!(app.prompt.as_ref().unwrap().error.is_some());
+
+    app.prompt
+        .as_mut()
+        .unwrap()
+        .input_mut()
+        .unwrap()
+        .backspace();
+    app.prompt
+        .as_mut()
+        .unwrap()
+        .input_mut()
+        .unwrap()
+        .insert('2');
+    app.submit_prompt();
+    assert_eq!(app.mode, AppMode::ProfileSelect);
+    assert_eq!(app.profiles[0].file_name, "laptop2");
+}
+
+#[test]
+fn test_save_prompt_confirms_overwrite_and_keeps_created_at() {
+    let dir = tempfile::tempdir().unwrap();
+    let monitors = vec![make_monitor("eDP-1", 0, 1920, 1080)];
+    let mut existing = Profile::from_monitors("desk".to_string(), None, &monitors);
+    existing.created_at = Some("2024-01-01T00:00:00+00:00".to_string());
+    let mut app = make_app_with_profiles(dir.path(), monitors, &[existing]);
+    app.unsaved_changes = true;
+
+    app.begin_save_profile();
+    let prompt = app.prompt.as_mut().unwrap();
+    "desk"
+        .chars()
+        .for_each(|c| prompt.input_mut().unwrap().insert(c));
+    prompt.next_field();
+    "Office"
+        .chars()
+        .for_each(|c| prompt.input_mut().unwrap().insert(c));
+    app.submit_prompt();
+
+    let AppMode::Confirm { action, .. } = app.mode.clone() else {
+        panic!("expected overwrite confirmation, got {:?}", app.mode);
+    };
+    assert_eq!(
+        action,
+        ConfirmAction::OverwriteProfile {
+            name: "desk".to_string(),
+            description: Some("Office".to_string()),
+        }
+    );
+    app.save_profile_as("desk", Some("Office".to_string()))
+        .unwrap();
+
+    let saved = config::load_profile(&dir.path().join("profiles/desk.toml")).unwrap();
+    assert_eq!(
+        saved.created_at.as_deref(),
+        Some("2024-01-01T00:00:00+00:00")
+    );
+    assert_ne!(saved.modified_at, saved.created_at);
+    assert_eq!(saved.description.as_deref(), Some("Office"));
+    assert!(!app.unsaved_changes);
+    assert_eq!(app.current_profile.as_deref(), Some("desk"));
+}
+
+#[test]
+fn test_save_prompt_writes_new_profile() {
+    let dir = tempfile::tempdir().unwrap();
+    let mut app =
+        make_app_with_profiles(dir.path(), vec![make_monitor("eDP-1", 0, 1920, 1080)], &[]);
+    app.begin_save_profile();
+    "travel"
+        .chars()
+        .for_each(|c| app.prompt.as_mut().unwrap().input_mut().unwrap().insert(c));
+    app.submit_prompt();
+    assert_eq!(app.mode, AppMode::Normal);
+    let saved = config::load_profile(&dir.path().join("profiles/travel.toml")).unwrap();
+    assert_eq!(saved.name, "travel");
+    assert_eq!(saved.description, None);
+}
+
+#[test]
+fn test_mirror_picker_rejects_cycles() {
+    let mut app = make_app(vec![
+        make_monitor("eDP-1", 0, 1920, 1080),
+        make_monitor("HDMI-A-1", 1920, 1920, 1080),
+        make_monitor("DP-1", 3840, 2560, 1440),
+    ]);
+    app.selected_monitor = 1;
+    assert_eq!(
+        app.picker_options(EditField::Mirror),
+        ["None", "eDP-1", "DP-1"]
+    );
+    app.open_picker(EditField::Mirror);
+    app.picker_move(EditField::Mirror, 1);
+    app.picker_select(EditField::Mirror);
+    assert_eq!(app.monitors[1].mirror_of.as_deref(), Some("eDP-1"));
+
+    // eDP-1 -> DP-1 -> HDMI-A-1 -> eDP-1 would loop.
+    app.selected_monitor = 2;
+    app.set_mirror(Some("HDMI-A-1".to_string())).unwrap();
+    app.selected_monitor = 0;
+    let history = app.history.len();
+    assert!(app.set_mirror(Some("DP-1".to_string())).is_err());
+    assert_eq!(app.monitors[0].mirror_of, None);
+    assert_eq!(app.history.len(), history);
+
+    app.selected_monitor = 1;
+    app.open_picker(EditField::Mirror);
+    assert_eq!(app.picker, Some(1));
+    app.picker_move(EditField::Mirror, -1);
+    app.picker_select(EditField::Mirror);
+    assert_eq!(app.monitors[1].mirror_of, None);
+}
+
+#[test]
+fn test_vrr_and_bitdepth_toggles_round_trip_through_profile() {
+    let mut app = make_app(vec![make_monitor("DP-1", 0, 2560, 1440)]);
+    app.toggle_field(EditField::Vrr);
+    app.toggle_field(EditField::Vrr);
+    app.toggle_field(EditField::Bitdepth);
+    assert_eq!(app.monitors[0].vrr, Some(Vrr::On));
+    assert_eq!(app.monitors[0].bitdepth, 10);
+    app.toggle_field(EditField::Vrr);
+    app.toggle_field(EditField::Vrr);
+    assert_eq!(app.monitors[0].vrr, None);
+    app.undo();
+    assert_eq!(app.monitors[0].vrr, Some(Vrr::FullscreenOnly));
+
+    let profile = Profile::from_monitors("hdr".to_string(), None, &app.monitors);
+    let profile: Profile = toml::from_str(&toml::to_string(&profile).unwrap()).unwrap();
+    let loaded = profile.to_monitors(&[make_monitor("DP-1", 0, 2560, 1440)]);
+    assert_eq!(
+        (loaded[0].vrr, loaded[0].bitdepth),
+        (Some(Vrr::FullscreenOnly), 10)
+    );
+}
+
+#[test]
+fn test_sdr_sliders_step_within_range_and_reset() {
+    let mut app = make_app(vec![make_monitor("DP-1", 0, 2560, 1440)]);
+    app.toggle_field(EditField::ColorMode);
+    app.toggle_field(EditField::ColorMode);
+    app.toggle_field(EditField::ColorMode);
+    assert_eq!(app.monitors[0].color_mode, Some(ColorMode::Hdr));
+
+    app.step_field(EditField::SdrBrightness, 2.0);
+    assert_eq!(app.monitors[0].sdr_brightness, Some(1.1));
+    for _ in 0..40 {
+        app.step_field(EditField::SdrBrightness, 1.0);
+    }
+    assert_eq!(app.monitors[0].sdr_brightness, Some(2.0));
+    app.step_field(EditField::SdrSaturation, -1.0);
+    assert_eq!(app.monitors[0].sdr_saturation, Some(0.95));
+
+    app.toggle_field(EditField::SdrBrightness);
+    assert_eq!(app.monitors[0].sdr_brightness, None);
+    app.undo();
+    assert_eq!(app.monitors[0].sdr_brightness, Some(2.0));
+}
+
+#[test]
+fn test_reserved_prompt_sets_and_clears_inset() {
+    let mut app = make_app(vec![make_monitor("DP-1", 0, 2560, 1440)]);
+    app.begin_edit_reserved();
+    assert_eq!(app.mode, AppMode::Prompt);
+    let prompt = app.prompt.as_mut().unwrap();
+    prompt.fields[0].1 = TextInput::new("32");
+    prompt.fields[2].1 = TextInput::new("x");
+    app.submit_prompt();
+    assert!(app.prompt.as_ref().unwrap().error.is_some());
+
+    app.prompt.as_mut().unwrap().fields[2].1 = TextInput::new("");
+    app.submit_prompt();
+    assert_eq!(
+        app.mode,
+        AppMode::Editing {
+            field: EditField::Reserved
+        }
+    );
+    assert_eq!(
+        app.monitors[0].reserved,
+        Some(Reserved {
+            top: 32,
+            bottom: 0,
+            left: 0,
+            right: 0
+        })
+    );
+
+    app.set_reserved(Some(Reserved::default()));
+    assert_eq!(app.monitors[0].reserved, Some(Reserved::default()));
+    app.set_reserved(None);
+    assert_eq!(app.history.len(), 2);
+    app.undo();
+    assert_eq!(app.monitors[0].reserved.map(|r| r.top), Some(32));
+}
+
+#[test]
+fn test_arrange_is_one_undo_step() {
+    let mut app = make_app(vec![
+        make_monitor("eDP-1", 4000, 1920, 1200),
+        make_monitor("DP-1", 0, 2560, 1440),
+        make_monitor("DP-2", 2600, 1920, 1080),
+    ]);
+    app.begin_arrange();
+    assert!(app.arrange_pending);
+    app.arrange(Arrangement::LShape);
+    assert!(!app.arrange_pending);
+    let layout: Vec<(i32, i32)> = app
+        .monitors
+        .iter()
+        .map(|m| (m.position.x, m.position.y))
+        .collect();
+    assert_eq!(layout, [(1280, 1440), (0, 0), (2560, 0)]);
+    assert!(app.unsaved_changes);
+    assert_eq!(app.history.len(), 1);
+
+    app.arrange(Arrangement::LShape);
+    assert_eq!(app.history.len(), 1);
+
+    assert!(app.undo());
+    assert_eq!(app.monitors[0].position.x, 4000);
+    assert_eq!(app.monitors[2].position.x, 2600);
+
+    let mut app = make_app(vec![make_monitor("DP-1", 0, 2560, 1440)]);
+    app.arrange(Arrangement::LShape);
+    assert!(app.error_message.is_some());
+    assert!(app.history.is_empty());
+}
+
+#[test]
+fn test_primary_toggle_keeps_one_primary() {
+    let mut app = make_app(vec![
+        make_monitor("eDP-1", 0, 1920, 1080),
+        make_monitor("DP-1", 1920, 1920, 1080),
+    ]);
+    app.toggle_field(EditField::Primary);
+    assert!(app.monitors[0].primary);
+    assert!(app.history.is_empty());
+    assert!(!app.unsaved_changes);
+
+    app.selected_monitor = 1;
+    app.toggle_field(EditField::Primary);
+    assert_eq!(
+        (app.monitors[0].primary, app.monitors[1].primary),
+        (false, true)
+    );
+    assert_eq!(app.history.len(), 1);
+}
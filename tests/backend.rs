@@ -0,0 +1,248 @@
+//! Backend tests against the in-process fake Hyprland.
+
+mod common;
+
+use common::{monitor_json, FakeHyprland};
+use hypr_monitor_tui::app::{App, AppMode};
+use hypr_monitor_tui::config::Theme;
+use hypr_monitor_tui::hyprland::{
+    ApplyError, HyprlandClient, Monitor, MonitorBackend, MonitorEvent,
+};
+use hypr_monitor_tui::hyprland::{ColorMode, Reserved};
+use std::io::{Read, Write};
+use std::os::unix::net::UnixStream;
+use std::path::PathBuf;
+use std::sync::mpsc;
+use std::time::Duration;
+
+fn two_monitors() -> Vec<serde_json::Value> {
+    let mut laptop = monitor_json("eDP-1", 0, 0, 1920, 1200, 60.0);
+    laptop["focused"] = true.into();
+    laptop["availableModes"] = serde_json::json!(["1920x1200@60.00Hz", "1920x1200@48.00Hz"]);
+    vec![laptop, monitor_json("DP-1", 1920, 0, 2560, 1440, 144.0)]
+}
+
+#[test]
+fn test_get_monitors_from_fake() {
+    let fake = FakeHyprland::start(two_monitors());
+    let monitors = fake.client().get_monitors().unwrap();
+    assert_eq!(monitors.len(), 2);
+    assert_eq!(monitors[0].name, "eDP-1");
+    assert!(monitors[0].primary);
+    assert_eq!(monitors[0].available_refresh_rates, vec![60.0, 48.0]);
+    assert_eq!(monitors[1].position.x, 1920);
+
+    let modes = fake.client().get_available_modes("eDP-1").unwrap();
+    assert_eq!(modes.len(), 2);
+}
+
+#[test]
+fn test_get_monitors_reads_display_options() {
+    let mut monitors = two_monitors();
+    monitors[1]["vrr"] = true.into();
+    monitors[1]["currentFormat"] = "XRGB2101010".into();
+    monitors[0]["colorManagementPreset"] = "srgb".into();
+    monitors[1]["colorManagementPreset"] = "hdr".into();
+    monitors[1]["sdrBrightness"] = 1.2.into();
+    let fake = FakeHyprland::start(monitors);
+    let monitors = fake.client().get_monitors().unwrap();
+    assert_eq!((monitors[0].vrr, monitors[0].bitdepth), (None, 8));
+    // The runtime VRR flag is not a setting and must not end up in rules.
+    assert_eq!((monitors[1].vrr, monitors[1].bitdepth), (None, 10));
+    assert_eq!(monitors[0].color_mode, None);
+    assert_eq!(monitors[1].color_mode, Some(ColorMode::Hdr));
+    assert_eq!(
+        (monitors[1].sdr_brightness, monitors[1].sdr_saturation),
+        (Some(1.2), None)
+    );
+}
+
+#[test]
+fn test_apply_all_sends_one_batch() {
+    let fake = FakeHyprland::start(two_monitors());
+    let client = fake.client();
+    let mut monitors = client.get_monitors().unwrap();
+    monitors[1].enabled = false;
+    client.apply_all(&monitors).unwrap();
+    assert_eq!(
+        fake.commands(),
+        [
+            "keyword monitor eDP-1,1920x1200@60.00,0x0,1",
+            "keyword monitor DP-1,disable",
+        ]
+    );
+}
+
+#[test]
+fn test_fake_batch_reply_matches_hyprland_framing() {
+    let fake = FakeHyprland::start(two_monitors());
+    let mut stream = UnixStream::connect(fake.dir().join(".socket.sock")).unwrap();
+    stream
+        .write_all(
+            b"[[BATCH]]/keyword monitor DP-1,2560x1440@144.00,0x0,1;\
+              /keyword monitor DP-1,addreserved,30,0,0,0;/keyword monitor eDP-1,disable",
+        )
+        .unwrap();
+    let mut reply = String::new();
+    stream.read_to_string(&mut reply).unwrap();
+    assert_eq!(
+        reply,
+        std::fs::read_to_string(
+            PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+                .join("tests/fixtures/hyprctl_batch_reply.txt")
+        )
+        .unwrap()
+    );
+}
+
+#[test]
+fn test_apply_all_reports_rejected_rule() {
+    let fake = FakeHyprland::start(two_monitors());
+    fake.reject("DP-1,2560x1440");
+    let client = fake.client();
+    let monitors = client.get_monitors().unwrap();
+    match client.apply_all(&monitors) {
+        Err(ApplyError::Partial { rejected, total }) => {
+            assert_eq!(total, 2);
+            assert_eq!(rejected.len(), 1);
+            assert!(rejected[0].rule.starts_with("DP-1,"));
+            assert_eq!(rejected[0].message, "invalid monitor rule");
+        }
+        other => panic!("expected partial failure, got {:?}", other),
+    }
+}
+
+#[test]
+fn test_subscribe_events_from_socket2() {
+    let fake = FakeHyprland::start(two_monitors());
+    let rx = fake.client().subscribe_events().unwrap();
+    fake.wait_for_subscriber();
+    fake.emit("workspace>>2");
+    fake.emit("monitoraddedv2>>3,HDMI-A-1,Fake HDMI");
+    let ev = rx.recv_timeout(Duration::from_secs(2)).unwrap();
+    assert_eq!(ev, MonitorEvent::Added("HDMI-A-1".to_string()));
+}
+
+#[test]
+fn test_tui_apply_and_revert_through_backend() {
+    let fake = FakeHyprland::start(two_monitors());
+    let client = fake.client();
+    let mut app = App::new(
+        client.get_monitors().unwrap(),
+        PathBuf::from("/nonexistent/config.toml"),
+        Theme::default(),
+    );
+    app.monitors[1].position.x = 2000;
+    app.apply_live(&client);
+    assert!(matches!(app.mode, AppMode::KeepSettings { .. }));
+
+    app.revert_live(&client);
+    let commands = fake.commands();
+    assert_eq!(commands.len(), 4);
+    assert!(commands[1].contains("2000x0"));
+    assert!(commands[3].contains("1920x0"));
+    assert_eq!(app.monitors[1].position.x, 1920);
+}
+
+#[test]
+fn test_tui_revert_clears_applied_reserved_area() {
+    let fake = FakeHyprland::start(two_monitors());
+    let client = fake.client();
+    let mut app = App::new(
+        client.get_monitors().unwrap(),
+        PathBuf::from("/nonexistent/config.toml"),
+        Theme::default(),
+    );
+    app.selected_monitor = 1;
+    app.set_reserved(Some(Reserved {
+        top: 30,
+        bottom: 0,
+        left: 0,
+        right: 0,
+    }));
+    app.apply_live(&client);
+    app.revert_live(&client);
+    let commands = fake.commands();
+    assert_eq!(commands[2], "keyword monitor DP-1,addreserved,30,0,0,0");
+    assert_eq!(commands[5], "keyword monitor DP-1,addreserved,0,0,0,0");
+
+    // Clearing the inset in the editor also sends a reset.
+    app.set_reserved(None);
+    assert_eq!(app.monitors[1].reserved, Some(Reserved::default()));
+}
+
+/// Reads from the fake but fails to deliver the batch, like a dropped socket.
+struct BatchFails<'a>(&'a HyprlandClient);
+
+impl MonitorBackend for BatchFails<'_> {
+    fn get_monitors(&self) -> anyhow::Result<Vec<Monitor>> {
+        self.0.get_monitors()
+    }
+
+    fn apply_all(&self, _monitors: &[Monitor]) -> Result<(), ApplyError> {
+        Err(ApplyError::Ipc(anyhow::anyhow!(
+            "Failed to connect to Hyprland socket"
+        )))
+    }
+
+    fn subscribe_events(&self) -> anyhow::Result<mpsc::Receiver<MonitorEvent>> {
+        self.0.subscribe_events()
+    }
+}
+
+#[test]
+fn test_tui_apply_failure_arms_no_countdown() {
+    let fake = FakeHyprland::start(two_monitors());
+    let client = fake.client();
+    let mut app = App::new(
+        client.get_monitors().unwrap(),
+        PathBuf::from("/nonexistent/config.toml"),
+        Theme::default(),
+    );
+    app.monitors[1].position.x = 2000;
+    app.apply_live(&BatchFails(&client));
+    assert_eq!(app.mode, AppMode::Normal);
+    assert!(app.revert_to.is_none());
+    assert!(app
+        .error_message
+        .as_deref()
+        .is_some_and(|e| e.contains("Not applied")));
+    // The failed apply leaves no undo step behind.
+    assert!(!app.undo());
+    assert_eq!(app.monitors[1].position.x, 2000);
+}
+
+#[test]
+fn test_tui_apply_refuses_overlapping_layout() {
+    let fake = FakeHyprland::start(two_monitors());
+    let client = fake.client();
+    let mut app = App::new(
+        client.get_monitors().unwrap(),
+        PathBuf::from("/nonexistent/config.toml"),
+        Theme::default(),
+    );
+    app.monitors[1].position.x = 1000;
+    app.apply_live(&client);
+    assert_eq!(app.mode, AppMode::Normal);
+    assert!(app
+        .error_message
+        .as_deref()
+        .is_some_and(|e| e.contains("overlap")));
+    assert!(fake.commands().is_empty());
+}
+
+#[test]
+fn test_tui_refresh_on_hotplug() {
+    let fake = FakeHyprland::start(two_monitors());
+    let client = fake.client();
+    let mut app = App::new(
+        client.get_monitors().unwrap(),
+        PathBuf::from("/nonexistent/config.toml"),
+        Theme::default(),
+    );
+    let mut monitors = two_monitors();
+    monitors.push(monitor_json("HDMI-A-1", 4480, 0, 1920, 1080, 60.0));
+    fake.set_monitors(monitors);
+    app.refresh_monitors(&client);
+    assert_eq!(app.monitors.len(), 3);
+}
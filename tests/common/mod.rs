@@ -0,0 +1,191 @@
+//! In-process fake Hyprland and shared fixtures for integration tests.
+//!
+//! Serves `.socket.sock` (answers `j/monitors`, records `keyword` commands) and
+//! `.socket2.sock` (emits events on demand) from a temporary instance directory.
+
+#![allow(dead_code)]
+
+use hypr_monitor_tui::hyprland::{HyprlandClient, Monitor, Position, Resolution, Transform};
+use serde_json::{json, Value};
+use std::io::{Read, Write};
+use std::os::unix::net::{UnixListener, UnixStream};
+use std::path::Path;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+#[derive(Default)]
+struct State {
+    monitors: Vec<Value>,
+    commands: Vec<String>,
+    reject: Vec<String>,
+}
+
+/// Fake compositor listening on a temporary instance directory.
+pub struct FakeHyprland {
+    dir: tempfile::TempDir,
+    state: Arc<Mutex<State>>,
+    subscribers: Arc<Mutex<Vec<UnixStream>>>,
+}
+
+impl FakeHyprland {
+    /// Start serving the given `j/monitors` entries.
+    pub fn start(monitors: Vec<Value>) -> Self {
+        let dir = tempfile::tempdir().expect("tempdir");
+        let state = Arc::new(Mutex::new(State {
+            monitors,
+            ..State::default()
+        }));
+        let subscribers = Arc::new(Mutex::new(Vec::new()));
+
+        let commands = UnixListener::bind(dir.path().join(".socket.sock")).expect("bind socket");
+        let st = Arc::clone(&state);
+        std::thread::spawn(move || {
+            for stream in commands.incoming().flatten() {
+                serve_command(stream, &st);
+            }
+        });
+
+        let events = UnixListener::bind(dir.path().join(".socket2.sock")).expect("bind socket2");
+        let subs = Arc::clone(&subscribers);
+        std::thread::spawn(move || {
+            for stream in events.incoming().flatten() {
+                subs.lock().expect("lock").push(stream);
+            }
+        });
+
+        Self {
+            dir,
+            state,
+            subscribers,
+        }
+    }
+
+    /// Instance directory holding both sockets.
+    pub fn dir(&self) -> &Path {
+        self.dir.path()
+    }
+
+    /// Client connected to this fake.
+    pub fn client(&self) -> HyprlandClient {
+        HyprlandClient::with_instance_dir(self.dir())
+    }
+
+    /// Commands received so far, e.g. "keyword monitor DP-1,disable".
+    pub fn commands(&self) -> Vec<String> {
+        self.state.lock().expect("lock").commands.clone()
+    }
+
+    /// Reply with an error to every monitor rule containing `pattern`.
+    pub fn reject(&self, pattern: &str) {
+        self.state
+            .lock()
+            .expect("lock")
+            .reject
+            .push(pattern.to_string());
+    }
+
+    /// Replace the monitors returned by `j/monitors`.
+    pub fn set_monitors(&self, monitors: Vec<Value>) {
+        self.state.lock().expect("lock").monitors = monitors;
+    }
+
+    /// Block until at least one client is subscribed to socket2.
+    pub fn wait_for_subscriber(&self) {
+        let start = Instant::now();
+        while self.subscribers.lock().expect("lock").is_empty() {
+            assert!(
+                start.elapsed() < Duration::from_secs(2),
+                "no socket2 subscriber"
+            );
+            std::thread::sleep(Duration::from_millis(5));
+        }
+    }
+
+    /// Send a raw `EVENT>>DATA` line to all socket2 subscribers.
+    pub fn emit(&self, line: &str) {
+        for s in self.subscribers.lock().expect("lock").iter_mut() {
+            let _ = writeln!(s, "{}", line);
+        }
+    }
+}
+
+fn serve_command(mut stream: UnixStream, state: &Mutex<State>) {
+    let mut buf = vec![0u8; 65536];
+    let n = stream.read(&mut buf).unwrap_or(0);
+    let request = String::from_utf8_lossy(&buf[..n]).to_string();
+    let mut st = state.lock().expect("lock");
+    let reply = if request.starts_with("j/monitors") {
+        Value::Array(st.monitors.clone()).to_string()
+    } else if let Some(batch) = request.strip_prefix("[[BATCH]]") {
+        batch
+            .split(';')
+            .map(|cmd| run_keyword(&mut st, cmd))
+            .collect::<Vec<_>>()
+            .join("\n\n\n")
+    } else {
+        run_keyword(&mut st, &request)
+    };
+    let _ = stream.write_all(reply.as_bytes());
+}
+
+fn run_keyword(st: &mut State, cmd: &str) -> String {
+    let cmd = cmd.trim_start_matches('/').to_string();
+    let rejected = st.reject.iter().any(|p| cmd.contains(p.as_str()));
+    st.commands.push(cmd);
+    if rejected {
+        "invalid monitor rule".to_string()
+    } else {
+        "ok".to_string()
+    }
+}
+
+/// A `j/monitors` entry with the fields hypr-monitor-tui reads.
+pub fn monitor_json(name: &str, x: i32, y: i32, width: u32, height: u32, hz: f32) -> Value {
+    json!({
+        "id": 0,
+        "name": name,
+        "description": format!("Fake {}", name),
+        "width": width,
+        "height": height,
+        "refreshRate": hz,
+        "x": x,
+        "y": y,
+        "activeWorkspace": { "id": 1, "name": "1" },
+        "reserved": [0, 0, 0, 0],
+        "scale": 1.0,
+        "transform": 0,
+        "focused": false,
+        "dpmsStatus": true,
+        "vrr": false,
+        "disabled": false,
+        "availableModes": [format!("{}x{}@{:.2}Hz", width, height, hz)],
+    })
+}
+
+/// Enabled 60 Hz monitor at scale 1 offering only its current mode; tests adjust the rest.
+pub fn monitor(name: &str, x: i32, y: i32, width: u32, height: u32) -> Monitor {
+    let res = Resolution { width, height };
+    Monitor {
+        name: name.to_string(),
+        description: name.to_string(),
+        position: Position { x, y },
+        resolution: res.clone(),
+        available_resolutions: vec![res],
+        refresh_rate: 60.0,
+        available_refresh_rates: vec![60.0],
+        available_modes: vec![],
+        scale: 1.0,
+        transform: Transform::Normal,
+        enabled: true,
+        primary: false,
+        mode_spec: None,
+        position_spec: None,
+        mirror_of: None,
+        vrr: None,
+        bitdepth: 8,
+        color_mode: None,
+        sdr_brightness: None,
+        sdr_saturation: None,
+        reserved: None,
+    }
+}
@@ -1,23 +1,22 @@
 //! Config generation tests.
 
-use hypr_monitor_tui::hyprland::{Monitor, Position, Resolution, Transform};
-use hypr_monitor_tui::hyprland::generate_config;
+mod common;
+
+use hypr_monitor_tui::hyprland::{
+    generate_config, generate_config_with, monitor_rule, parse_monitor_rule, read_monitor_rules,
+    reserved_rule, ConfigSyntax, ExportOptions,
+};
+use hypr_monitor_tui::hyprland::{ColorMode, Monitor, Reserved, Transform, Vrr};
+use pretty_assertions::assert_eq;
+use std::path::PathBuf;
 
 fn make_monitor(name: &str, x: i32, y: i32, w: u32, h: u32, hz: f32, scale: f32) -> Monitor {
-    let res = Resolution { width: w, height: h };
-    Monitor {
-        name: name.to_string(),
-        description: name.to_string(),
-        position: Position { x, y },
-        resolution: res.clone(),
-        available_resolutions: vec![res],
-        refresh_rate: hz,
-        available_refresh_rates: vec![hz],
-        scale,
-        transform: Transform::Normal,
-        enabled: true,
-        primary: name == "DP-1",
-    }
+    let mut m = common::monitor(name, x, y, w, h);
+    m.refresh_rate = hz;
+    m.available_refresh_rates = vec![hz];
+    m.scale = scale;
+    m.primary = name == "DP-1";
+    m
 }
 
 #[test]
@@ -44,10 +43,228 @@ fn test_generate_config_two_monitors() {
 }
 
 #[test]
-fn test_generate_config_disabled_omitted() {
+fn test_generate_config_disabled_emits_disable() {
     let mut m = make_monitor("DP-1", 0, 0, 1920, 1080, 60.0, 1.0);
     m.enabled = false;
     let monitors = vec![m];
     let out = generate_config(&monitors);
-    assert!(!out.contains("monitor=DP-1"));
+    assert!(out.lines().any(|l| l == "monitor=DP-1,disable"));
+    assert!(!out.contains("1920x1080"));
+}
+
+#[test]
+fn test_monitor_rule_exact_format() {
+    let m = make_monitor("DP-1", 2560, -120, 2560, 1440, 143.97, 1.25);
+    assert_eq!(monitor_rule(&m), "DP-1,2560x1440@143.97,2560x-120,1.25");
+}
+
+#[test]
+fn test_monitor_rule_with_transform() {
+    let mut m = make_monitor("HDMI-A-1", 0, 0, 1920, 1080, 60.0, 1.0);
+    m.transform = Transform::Rotate90;
+    assert_eq!(
+        monitor_rule(&m),
+        "HDMI-A-1,1920x1080@60.00,0x0,1,transform,1"
+    );
+    m.transform = Transform::Flipped270;
+    assert_eq!(
+        monitor_rule(&m),
+        "HDMI-A-1,1920x1080@60.00,0x0,1,transform,7"
+    );
+}
+
+#[test]
+fn test_generate_config_exact_output() {
+    let mut laptop = make_monitor("eDP-1", 0, 1440, 1920, 1200, 60.0, 1.5);
+    laptop.transform = Transform::Rotate180;
+    let mut off = make_monitor("HDMI-A-1", 0, 0, 1920, 1080, 60.0, 1.0);
+    off.enabled = false;
+    let monitors = vec![
+        make_monitor("DP-1", 0, 0, 2560, 1440, 144.0, 1.0),
+        laptop,
+        off,
+    ];
+    let expected = "\
+# Generated by hypr-monitor-tui
+# https://github.com/Ninso112/hypr-monitor-tui
+
+monitor=DP-1,2560x1440@144.00,0x0,1
+monitor=eDP-1,1920x1200@60.00,0x1440,1.5,transform,2
+monitor=HDMI-A-1,disable";
+    assert_eq!(generate_config(&monitors), expected);
+}
+
+#[test]
+fn test_generate_config_by_description() {
+    let mut m = make_monitor("DP-1", 0, 0, 2560, 1440, 144.0, 1.0);
+    m.description = "Dell Inc. DELL U2720Q AAA".to_string();
+    let options = ExportOptions {
+        by_description: true,
+        ..Default::default()
+    };
+    let out = generate_config_with(&[m], &options);
+    assert!(out
+        .lines()
+        .any(|l| l == "monitor=desc:Dell Inc. DELL U2720Q AAA,2560x1440@144.00,0x0,1"));
+}
+
+/// Layout shared by the golden files in tests/golden/.
+fn golden_monitors() -> Vec<Monitor> {
+    let mut laptop = make_monitor("eDP-1", 0, 0, 2880, 1800, 90.0, 2.0);
+    laptop.transform = Transform::Rotate90;
+    laptop.description = String::new();
+    let mut dell = make_monitor("DP-1", 900, 0, 3840, 2160, 60.0, 1.5);
+    dell.description = "Dell Inc. DELL U2720Q AAA".to_string();
+    dell.reserved = Some(Reserved {
+        top: 32,
+        bottom: 0,
+        left: 0,
+        right: 0,
+    });
+    let mut tv = make_monitor("HDMI-A-1", 0, 0, 1920, 1080, 60.0, 1.0);
+    tv.enabled = false;
+    tv.description = String::new();
+    vec![laptop, dell, tv]
+}
+
+fn golden_path(name: &str) -> PathBuf {
+    PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+        .join("tests/golden")
+        .join(name)
+}
+
+fn check_golden(name: &str, syntax: ConfigSyntax) {
+    let options = ExportOptions {
+        by_description: true,
+        syntax,
+    };
+    let expected = std::fs::read_to_string(golden_path(name)).unwrap();
+    assert_eq!(generate_config_with(&golden_monitors(), &options), expected);
+}
+
+#[test]
+fn test_golden_legacy_syntax() {
+    check_golden("monitors.legacy.conf", ConfigSyntax::Legacy);
+}
+
+#[test]
+fn test_golden_monitorv2_syntax() {
+    check_golden("monitors.v2.conf", ConfigSyntax::V2);
+}
+
+#[test]
+fn test_golden_files_parse_to_the_same_rules() {
+    let legacy = read_monitor_rules(&golden_path("monitors.legacy.conf")).unwrap();
+    let v2 = read_monitor_rules(&golden_path("monitors.v2.conf")).unwrap();
+    // The legacy reserved area is a rule of its own; the block carries it inline.
+    let (addreserved, mut legacy): (Vec<_>, Vec<_>) =
+        legacy.into_iter().partition(|r| r.addreserved);
+    assert_eq!(addreserved.len(), 1);
+    legacy[1].reserved = addreserved[0].reserved;
+    assert_eq!(legacy.len(), 3);
+    assert_eq!(legacy, v2);
+    assert!(v2[2].disabled);
+    assert_eq!(v2[0].transform, Transform::Rotate90);
+    assert_eq!(v2[1].scale, Some(1.5));
+}
+
+#[test]
+fn test_monitor_rule_with_mirror() {
+    let mut m = make_monitor("HDMI-A-1", 0, 0, 1920, 1080, 60.0, 1.0);
+    m.mirror_of = Some("eDP-1".to_string());
+    assert_eq!(
+        monitor_rule(&m),
+        "HDMI-A-1,1920x1080@60.00,0x0,1,mirror,eDP-1"
+    );
+}
+
+#[test]
+fn test_monitor_rule_with_vrr_and_bitdepth() {
+    let mut m = make_monitor("DP-1", 0, 0, 2560, 1440, 144.0, 1.0);
+    m.vrr = Some(Vrr::FullscreenOnly);
+    m.bitdepth = 10;
+    assert_eq!(
+        monitor_rule(&m),
+        "DP-1,2560x1440@144.00,0x0,1,bitdepth,10,vrr,2"
+    );
+    let opts = ExportOptions {
+        syntax: ConfigSyntax::V2,
+        ..Default::default()
+    };
+    let block = generate_config_with(std::slice::from_ref(&m), &opts);
+    assert!(block.contains("  bitdepth = 10\n  vrr = 2\n"));
+    let rule = parse_monitor_rule(&monitor_rule(&m)).unwrap();
+    assert_eq!(
+        (rule.vrr, rule.bitdepth),
+        (Some(Vrr::FullscreenOnly), Some(10))
+    );
+}
+
+#[test]
+fn test_monitor_rule_with_colour_management() {
+    let mut m = make_monitor("DP-1", 0, 0, 2560, 1440, 144.0, 1.0);
+    m.color_mode = Some(ColorMode::HdrEdid);
+    m.sdr_brightness = Some(1.2);
+    m.sdr_saturation = Some(0.95);
+    assert_eq!(
+        monitor_rule(&m),
+        "DP-1,2560x1440@144.00,0x0,1,cm,hdredid,sdrbrightness,1.2,sdrsaturation,0.95"
+    );
+    let rule = parse_monitor_rule(&monitor_rule(&m)).unwrap();
+    assert_eq!(rule.color_mode, Some(ColorMode::HdrEdid));
+    assert_eq!(
+        (rule.sdr_brightness, rule.sdr_saturation),
+        (Some(1.2), Some(0.95))
+    );
+}
+
+#[test]
+fn test_reserved_area_is_an_addreserved_rule_or_block_key() {
+    let mut bar = make_monitor("DP-1", 0, 0, 2560, 1440, 144.0, 1.0);
+    bar.reserved = Some(Reserved {
+        top: 30,
+        bottom: 0,
+        left: 0,
+        right: 0,
+    });
+    let monitors = vec![
+        bar,
+        make_monitor("HDMI-A-1", 2560, 0, 1920, 1080, 60.0, 1.0),
+    ];
+    assert_eq!(
+        reserved_rule(&monitors[0]).as_deref(),
+        Some("DP-1,addreserved,30,0,0,0")
+    );
+    assert_eq!(reserved_rule(&monitors[1]), None);
+
+    let legacy = generate_config(&monitors);
+    assert!(legacy.contains(
+        "monitor=DP-1,2560x1440@144.00,0x0,1\nmonitor=DP-1,addreserved,30,0,0,0\nmonitor=HDMI-A-1,"
+    ));
+    let opts = ExportOptions {
+        syntax: ConfigSyntax::V2,
+        ..Default::default()
+    };
+    let v2 = generate_config_with(&monitors, &opts);
+    assert!(v2.contains("  scale = 1\n  addreserved = 30, 0, 0, 0\n}"));
+    assert!(!v2.contains("monitor="));
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("monitors.conf");
+    std::fs::write(&path, &v2).unwrap();
+    let rules = read_monitor_rules(&path).unwrap();
+    assert_eq!(rules.len(), 2);
+    assert_eq!(rules[0].reserved, monitors[0].reserved);
+
+    let mut off = monitors[0].clone();
+    off.enabled = false;
+    assert_eq!(reserved_rule(&off), None);
+
+    // A cleared inset resets Hyprland's but is not exported.
+    let mut cleared = monitors.clone();
+    cleared[0].reserved = Some(Reserved::default());
+    assert_eq!(
+        reserved_rule(&cleared[0]).as_deref(),
+        Some("DP-1,addreserved,0,0,0,0")
+    );
+    assert!(!generate_config(&cleared).contains("addreserved"));
 }
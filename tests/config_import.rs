@@ -0,0 +1,156 @@
+//! Importing monitor= rules from hyprland.conf into profiles.
+
+mod common;
+
+use hypr_monitor_tui::config::{self, Profile};
+use hypr_monitor_tui::hyprland::{
+    read_monitor_rules, OutputSelector, Position, PositionSpec, Reserved,
+};
+
+#[test]
+fn test_import_follows_source_and_later_rules_win() {
+    let dir = tempfile::tempdir().unwrap();
+    std::fs::write(
+        dir.path().join("hyprland.conf"),
+        "# main config\n\
+         monitor = ,preferred,auto,1\n\
+         monitor = eDP-1, 1920x1080@60, 0x0, 1 # laptop\n\
+         source = ./monitors.conf\n\
+         source = ./hyprland.conf\n\
+         source = ./missing.conf\n\
+         monitor = HDMI-A-1, bogus\n",
+    )
+    .unwrap();
+    std::fs::write(
+        dir.path().join("monitors.conf"),
+        "monitor=desc:Dell Inc. DELL U2720Q,3840x2160@60,auto,1.5,transform,1\n\
+         monitor=eDP-1,disable\n",
+    )
+    .unwrap();
+
+    let rules = read_monitor_rules(&dir.path().join("hyprland.conf")).unwrap();
+    assert_eq!(rules.len(), 4);
+    assert_eq!(rules[0].output, OutputSelector::Any);
+
+    let profile = Profile::from_rules("imported".to_string(), None, &rules);
+    assert_eq!(profile.monitors.len(), 2);
+    let laptop = &profile.monitors[0];
+    assert_eq!(laptop.name, "eDP-1");
+    assert!(!laptop.enabled);
+    let dell = &profile.monitors[1];
+    assert_eq!(dell.name, "");
+    assert_eq!(dell.description.as_deref(), Some("Dell Inc. DELL U2720Q"));
+    assert_eq!(dell.resolution.as_deref(), Some("3840x2160"));
+    assert_eq!(dell.refresh_rate, Some(60.0));
+    assert_eq!(dell.position, PositionSpec::Auto);
+    assert_eq!(dell.transform, "1");
+}
+
+#[test]
+fn test_import_keeps_symbolic_modes_and_positions() {
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("hyprland.conf");
+    std::fs::write(
+        &path,
+        "monitor=eDP-1,2880x1800@90,0x0,2\nmonitor=DP-1,highrr,auto,1\nmonitor=DP-2,preferred,auto-left,1\n",
+    )
+    .unwrap();
+    let profile = Profile::from_rules("p".to_string(), None, &read_monitor_rules(&path).unwrap());
+    let positions: Vec<_> = profile
+        .monitors
+        .iter()
+        .map(|m| m.position.clone())
+        .collect();
+    assert_eq!(
+        positions,
+        [
+            PositionSpec::Explicit(Position { x: 0, y: 0 }),
+            PositionSpec::Auto,
+            PositionSpec::AutoLeft,
+        ]
+    );
+    assert_eq!(profile.monitors[1].resolution.as_deref(), Some("highrr"));
+    assert_eq!(profile.monitors[2].resolution.as_deref(), Some("preferred"));
+    assert_eq!(profile.monitors[2].refresh_rate, None);
+}
+
+#[test]
+fn test_import_keeps_auto_scale() {
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("hyprland.conf");
+    std::fs::write(
+        &path,
+        "monitor=DP-1,preferred,auto,auto\nmonitor=eDP-1,preferred,auto,2\n",
+    )
+    .unwrap();
+    let profile = Profile::from_rules("p".to_string(), None, &read_monitor_rules(&path).unwrap());
+    assert_eq!(profile.monitors[0].scale, None);
+    assert_eq!(profile.monitors[1].scale, Some(2.0));
+
+    // Saved without a scale, and applied with the scale Hyprland picked.
+    let saved = dir.path().join("p.toml");
+    config::save_profile(&saved, &profile).unwrap();
+    let profile = config::load_profile(&saved).unwrap();
+    assert_eq!(profile.monitors[0].scale, None);
+    let mut dp = common::monitor("DP-1", 0, 0, 3840, 2160);
+    dp.scale = 1.5;
+    let monitors = profile.to_monitors(&[dp, common::monitor("eDP-1", 0, 0, 2880, 1800)]);
+    assert_eq!(monitors[0].scale, 1.5);
+    assert_eq!(monitors[1].scale, 2.0);
+}
+
+#[test]
+fn test_import_attaches_addreserved_to_its_output() {
+    let dir = tempfile::tempdir().unwrap();
+    let conf = dir.path().join("hyprland.conf");
+    std::fs::write(
+        &conf,
+        "monitor=DP-1,addreserved,10,0,0,0\n\
+         monitor=DP-1,2560x1440@144,0x0,1\n\
+         monitor=DP-1,addreserved,40,0,0,0\n\
+         monitor=HDMI-A-1,1920x1080@60,2560x0,1\n\
+         monitor=eDP-2,addreserved,40,0,0,0\n",
+    )
+    .unwrap();
+
+    let rules = read_monitor_rules(&conf).unwrap();
+    assert_eq!(rules.len(), 5);
+    let profile = Profile::from_rules("bar".to_string(), None, &rules);
+    assert_eq!(profile.monitors.len(), 2);
+    assert_eq!(
+        profile.monitors[0].reserved,
+        Some(Reserved {
+            top: 40,
+            bottom: 0,
+            left: 0,
+            right: 0
+        })
+    );
+    assert_eq!(profile.monitors[0].resolution.as_deref(), Some("2560x1440"));
+    assert_eq!(profile.monitors[1].reserved, None);
+}
+
+#[test]
+fn test_import_reads_addreserved_inside_monitorv2_block() {
+    let dir = tempfile::tempdir().unwrap();
+    let conf = dir.path().join("hyprland.conf");
+    std::fs::write(
+        &conf,
+        "monitorv2 {\n  output = DP-1\n  mode = 2560x1440@144\n  addreserved = 0, 0, 48, 0\n}\n\
+         monitorv2 {\n  output = DP-2\n  addreserved = 1, 2\n}\n",
+    )
+    .unwrap();
+
+    let rules = read_monitor_rules(&conf).unwrap();
+    assert_eq!(rules.len(), 1);
+    let profile = Profile::from_rules("bar".to_string(), None, &rules);
+    assert_eq!(
+        profile.monitors[0].reserved,
+        Some(Reserved {
+            top: 0,
+            bottom: 0,
+            left: 48,
+            right: 0
+        })
+    );
+}
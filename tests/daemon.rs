@@ -0,0 +1,133 @@
+//! Profile auto-switching tests.
+
+mod common;
+
+use common::{monitor_json, FakeHyprland};
+use hypr_monitor_tui::config::{save_profile, Profile};
+use hypr_monitor_tui::daemon::{apply_best, best_profile, run, DaemonOptions};
+use hypr_monitor_tui::hyprland::{Monitor, MonitorBackend};
+use std::time::Duration;
+
+fn make_monitor(name: &str, x: i32) -> Monitor {
+    common::monitor(name, x, 0, 1920, 1080)
+}
+
+fn profile(name: &str, outputs: &[&str]) -> Profile {
+    let monitors: Vec<Monitor> = outputs.iter().map(|n| make_monitor(n, 0)).collect();
+    Profile::from_monitors(name.to_string(), None, &monitors)
+}
+
+#[test]
+fn test_best_profile_prefers_most_connected_outputs() {
+    let profiles = vec![
+        profile("laptop", &["eDP-1"]),
+        profile("docked", &["eDP-1", "DP-1"]),
+        profile("office", &["eDP-1", "HDMI-A-1"]),
+    ];
+    let undocked = [make_monitor("eDP-1", 0)];
+    let docked = [make_monitor("eDP-1", 0), make_monitor("DP-1", 1920)];
+    assert_eq!(best_profile(&profiles, &undocked).unwrap().name, "laptop");
+    assert_eq!(best_profile(&profiles, &docked).unwrap().name, "docked");
+    assert!(best_profile(&profiles, &[make_monitor("DP-2", 0)]).is_none());
+}
+
+#[test]
+fn test_apply_best_against_fake_hyprland() {
+    let fake = FakeHyprland::start(vec![
+        monitor_json("eDP-1", 0, 0, 1920, 1080, 60.0),
+        monitor_json("DP-1", 1920, 0, 1920, 1080, 60.0),
+    ]);
+    let client = fake.client();
+    let dir = tempfile::tempdir().unwrap();
+    let mut docked = client.get_monitors().unwrap();
+    docked[0].position.y = 1080;
+    let p = Profile::from_monitors("docked".to_string(), None, &docked);
+    save_profile(&dir.path().join("docked.toml"), &p).unwrap();
+    save_profile(
+        &dir.path().join("laptop.toml"),
+        &profile("laptop", &["eDP-1"]),
+    )
+    .unwrap();
+
+    let mut opts = DaemonOptions {
+        profiles_dir: dir.path().to_path_buf(),
+        debounce: Duration::from_millis(10),
+        dry_run: true,
+    };
+    assert_eq!(
+        apply_best(&client, &opts).unwrap().as_deref(),
+        Some("docked")
+    );
+    assert!(fake.commands().is_empty());
+
+    opts.dry_run = false;
+    apply_best(&client, &opts).unwrap();
+    let commands = fake.commands();
+    assert_eq!(commands.len(), 2);
+    assert!(commands[0].contains("eDP-1,1920x1080@60.00,0x1080"));
+}
+
+#[test]
+fn test_run_keeps_going_after_failed_startup_apply() {
+    let fake = FakeHyprland::start(vec![monitor_json("eDP-1", 0, 0, 1920, 1080, 60.0)]);
+    fake.reject("eDP-1,");
+    let dir = tempfile::tempdir().unwrap();
+    save_profile(
+        &dir.path().join("laptop.toml"),
+        &profile("laptop", &["eDP-1"]),
+    )
+    .unwrap();
+    let docked = [make_monitor("eDP-1", 0), make_monitor("DP-1", 1920)];
+    let p = Profile::from_monitors("docked".to_string(), None, &docked);
+    save_profile(&dir.path().join("docked.toml"), &p).unwrap();
+    let opts = DaemonOptions {
+        profiles_dir: dir.path().to_path_buf(),
+        debounce: Duration::from_millis(10),
+        dry_run: false,
+    };
+    let client = fake.client();
+    let daemon = std::thread::spawn(move || run(&client, &opts));
+
+    fake.wait_for_subscriber();
+    let start = std::time::Instant::now();
+    while fake.commands().is_empty() {
+        assert!(start.elapsed() < Duration::from_secs(2), "no startup apply");
+        std::thread::sleep(Duration::from_millis(10));
+    }
+    assert!(!daemon.is_finished());
+
+    fake.set_monitors(vec![
+        monitor_json("eDP-1", 0, 0, 1920, 1080, 60.0),
+        monitor_json("DP-1", 1920, 0, 1920, 1080, 60.0),
+    ]);
+    fake.emit("monitoraddedv2>>1,DP-1,Fake DP");
+    while fake.commands().len() < 3 {
+        assert!(
+            start.elapsed() < Duration::from_secs(2),
+            "no apply after hotplug"
+        );
+        std::thread::sleep(Duration::from_millis(10));
+    }
+    assert!(fake.commands()[2].contains("DP-1,"));
+}
+
+#[test]
+fn test_apply_best_skips_overlapping_profile() {
+    let fake = FakeHyprland::start(vec![
+        monitor_json("eDP-1", 0, 0, 1920, 1080, 60.0),
+        monitor_json("DP-1", 1920, 0, 1920, 1080, 60.0),
+    ]);
+    let dir = tempfile::tempdir().unwrap();
+    save_profile(
+        &dir.path().join("docked.toml"),
+        &profile("docked", &["eDP-1", "DP-1"]),
+    )
+    .unwrap();
+    let opts = DaemonOptions {
+        profiles_dir: dir.path().to_path_buf(),
+        debounce: Duration::from_millis(10),
+        dry_run: false,
+    };
+    assert_eq!(apply_best(&fake.client(), &opts).unwrap(), None);
+    assert!(fake.commands().is_empty());
+}
@@ -1,6 +1,10 @@
 //! Monitor data parsing tests.
 
-use hypr_monitor_tui::hyprland::{Resolution, Transform};
+mod common;
+
+use hypr_monitor_tui::hyprland::{
+    is_valid_scale, unique_resolutions, valid_scales_near, Mode, Resolution, Transform,
+};
 
 #[test]
 fn test_resolution_to_string() {
@@ -31,3 +35,62 @@ fn test_transform_from_hyprland_str() {
     assert!(matches!(Transform::from_hyprland_str("0"), Transform::Normal));
     assert!(matches!(Transform::from_hyprland_str("1"), Transform::Rotate90));
 }
+
+fn mode(width: u32, height: u32, refresh_rate: f32) -> Mode {
+    Mode {
+        width,
+        height,
+        refresh_rate,
+    }
+}
+
+#[test]
+fn test_modes_keyed_per_resolution() {
+    let modes = vec![
+        mode(2560, 1440, 59.95),
+        mode(2560, 1440, 143.97),
+        mode(1920, 1080, 60.0),
+        mode(2560, 1440, 120.0),
+    ];
+    let res = unique_resolutions(&modes);
+    assert_eq!(res.len(), 2);
+    assert_eq!(res[0].to_string(), "2560x1440");
+    assert_eq!(res[1].to_string(), "1920x1080");
+
+    let mut m = common::monitor("DP-1", 0, 0, 2560, 1440);
+    m.available_resolutions = res.clone();
+    m.refresh_rate = 143.97;
+    m.available_modes = modes;
+    assert_eq!(m.refresh_rates_for(&res[0]), vec![143.97, 120.0, 59.95]);
+    assert_eq!(m.refresh_rates_for(&res[1]), vec![60.0]);
+
+    // 2560x1440 at scale 1.25 is 2048x1152 logical; rotating swaps the axes.
+    assert_eq!(m.logical_size(), (2560, 1440));
+    m.scale = 1.25;
+    assert_eq!(m.logical_size(), (2048, 1152));
+    m.transform = Transform::Flipped270;
+    assert_eq!(m.logical_size(), (1152, 2048));
+    m.transform = Transform::Rotate180;
+    assert_eq!(m.logical_size(), (2048, 1152));
+}
+
+#[test]
+fn test_scale_validator() {
+    let qhd = Resolution {
+        width: 2560,
+        height: 1440,
+    };
+    assert!(is_valid_scale(&qhd, 1.0));
+    assert!(is_valid_scale(&qhd, 1.25));
+    assert!(is_valid_scale(&qhd, 4.0 / 3.0));
+    assert!(!is_valid_scale(&qhd, 1.33));
+    assert!(!is_valid_scale(&qhd, 1.5));
+    assert_eq!(valid_scales_near(&qhd, 1.5, 2), vec![4.0 / 3.0, 1.6]);
+
+    let fhd = Resolution {
+        width: 1920,
+        height: 1080,
+    };
+    assert!(is_valid_scale(&fhd, 1.5));
+    assert_eq!(valid_scales_near(&fhd, 1.5, 3), vec![4.0 / 3.0, 1.5, 1.6]);
+}
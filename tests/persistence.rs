@@ -0,0 +1,107 @@
+//! Persisting generated rules into hyprland.conf.
+
+use hypr_monitor_tui::hyprland::{persist, write_atomic, PersistMode, PersistTarget};
+use std::path::Path;
+
+fn target(dir: &Path, mode: PersistMode) -> PersistTarget {
+    PersistTarget {
+        mode,
+        hyprland_conf: dir.join("hyprland.conf"),
+        monitors_conf: dir.join("monitors.conf"),
+    }
+}
+
+fn backups(dir: &Path, prefix: &str) -> usize {
+    std::fs::read_dir(dir)
+        .unwrap()
+        .filter_map(|e| e.ok())
+        .filter(|e| {
+            let name = e.file_name().to_string_lossy().into_owned();
+            name.starts_with(prefix) && name.ends_with(".bak")
+        })
+        .count()
+}
+
+#[test]
+fn test_source_mode_writes_monitors_conf_and_source_line_once() {
+    let dir = tempfile::tempdir().unwrap();
+    let t = target(dir.path(), PersistMode::Source);
+    std::fs::write(&t.hyprland_conf, "exec-once = waybar\n").unwrap();
+
+    let report = persist(&t, "monitor=DP-1,2560x1440@144.00,0x0,1").unwrap();
+    assert_eq!(
+        report.written,
+        [t.monitors_conf.clone(), t.hyprland_conf.clone()]
+    );
+    assert_eq!(
+        std::fs::read_to_string(&t.monitors_conf).unwrap(),
+        "monitor=DP-1,2560x1440@144.00,0x0,1\n"
+    );
+    let conf = std::fs::read_to_string(&t.hyprland_conf).unwrap();
+    assert_eq!(
+        conf,
+        format!(
+            "exec-once = waybar\n\nsource = {}\n",
+            t.monitors_conf.display()
+        )
+    );
+    assert_eq!(backups(dir.path(), "hyprland.conf."), 1);
+
+    // Unchanged content is not rewritten; a changed block only touches monitors.conf.
+    assert!(persist(&t, "monitor=DP-1,2560x1440@144.00,0x0,1")
+        .unwrap()
+        .written
+        .is_empty());
+    let report = persist(&t, "monitor=DP-1,disable").unwrap();
+    assert_eq!(report.written, vec![t.monitors_conf.clone()]);
+    assert_eq!(report.backups.len(), 1);
+    assert_eq!(std::fs::read_to_string(&t.hyprland_conf).unwrap(), conf);
+}
+
+#[test]
+fn test_managed_mode_replaces_block_in_place() {
+    let dir = tempfile::tempdir().unwrap();
+    let t = target(dir.path(), PersistMode::Managed);
+    let original = "general {\n}\n# BEGIN hypr-monitor-tui\nmonitor=DP-1,disable\n# END hypr-monitor-tui\ninput {\n}\n";
+    std::fs::write(&t.hyprland_conf, original).unwrap();
+
+    let report = persist(&t, "monitor=DP-1,1920x1080@60.00,0x0,1").unwrap();
+    assert_eq!(report.written, vec![t.hyprland_conf.clone()]);
+    assert_eq!(
+        std::fs::read_to_string(&t.hyprland_conf).unwrap(),
+        "general {\n}\n# BEGIN hypr-monitor-tui\nmonitor=DP-1,1920x1080@60.00,0x0,1\n# END hypr-monitor-tui\ninput {\n}\n"
+    );
+    assert_eq!(
+        std::fs::read_to_string(&report.backups[0]).unwrap(),
+        original
+    );
+    assert!(!t.monitors_conf.exists());
+}
+
+#[test]
+fn test_write_atomic_keeps_symlink_mode_and_every_backup() {
+    use std::os::unix::fs::PermissionsExt;
+    let dir = tempfile::tempdir().unwrap();
+    let dotfiles = dir.path().join("dotfiles");
+    std::fs::create_dir(&dotfiles).unwrap();
+    let real = dotfiles.join("hyprland.conf");
+    std::fs::write(&real, "original\n").unwrap();
+    std::fs::set_permissions(&real, std::fs::Permissions::from_mode(0o600)).unwrap();
+    let link = dir.path().join("hyprland.conf");
+    std::os::unix::fs::symlink(&real, &link).unwrap();
+
+    let first = write_atomic(&link, "first\n").unwrap().unwrap();
+    let second = write_atomic(&link, "second\n").unwrap().unwrap();
+    assert!(std::fs::symlink_metadata(&link)
+        .unwrap()
+        .file_type()
+        .is_symlink());
+    assert_eq!(std::fs::read_to_string(&real).unwrap(), "second\n");
+    assert_eq!(
+        std::fs::metadata(&real).unwrap().permissions().mode() & 0o777,
+        0o600
+    );
+    assert_ne!(first, second);
+    assert_eq!(std::fs::read_to_string(&first).unwrap(), "original\n");
+    assert_eq!(std::fs::read_to_string(&second).unwrap(), "first\n");
+}
@@ -1,5 +1,18 @@
 //! Position/snap calculation tests.
 
+mod common;
+
+use hypr_monitor_tui::hyprland::{
+    arrange, overlaps, snap_to_neighbours, validate_layout, Arrangement, LayoutIssueKind, Monitor,
+    Position, PositionSpec, Reserved, Severity, Transform,
+};
+use hypr_monitor_tui::ui::{monitor_at, GridView};
+use ratatui::layout::Rect;
+
+fn make_monitor(name: &str, x: i32, y: i32, w: u32, h: u32) -> Monitor {
+    common::monitor(name, x, y, w, h)
+}
+
 #[test]
 fn test_position_bounds() {
     let positions = [(0, 0), (2560, 0), (0, 1440)];
@@ -14,6 +27,7 @@ fn test_position_bounds() {
 }
 
 #[test]
+#[allow(clippy::manual_clamp)]
 fn test_scale_fit() {
     let total_w = 2560 + 1920;
     let total_h = 1440;
@@ -25,3 +39,264 @@ fn test_scale_fit() {
     assert!(scale > 0.0);
     assert!(scale <= 1.0);
 }
+
+#[test]
+fn test_snap_flush_to_right_edge() {
+    let monitors = vec![
+        make_monitor("DP-1", 0, 0, 2560, 1440),
+        make_monitor("HDMI-A-1", 2570, 5, 1920, 1080),
+    ];
+    let pos = snap_to_neighbours(&monitors, 1, 20);
+    assert_eq!(pos, Position { x: 2560, y: 0 });
+}
+
+#[test]
+fn test_snap_ignores_edges_beyond_threshold() {
+    let monitors = vec![
+        make_monitor("DP-1", 0, 0, 2560, 1440),
+        make_monitor("HDMI-A-1", 2600, 300, 1920, 1080),
+    ];
+    let pos = snap_to_neighbours(&monitors, 1, 10);
+    assert_eq!(pos, Position { x: 2600, y: 300 });
+}
+
+#[test]
+fn test_snap_threshold_is_exclusive() {
+    // Nudging away from a flush edge by exactly one step must not snap back.
+    let monitors = vec![
+        make_monitor("DP-1", 0, 0, 2560, 1440),
+        make_monitor("HDMI-A-1", 2570, 1440, 1920, 1080),
+    ];
+    let pos = snap_to_neighbours(&monitors, 1, 10);
+    assert_eq!(pos.x, 2570);
+}
+
+#[test]
+fn test_grid_hit_test_and_inverse_mapping() {
+    let monitors = vec![
+        make_monitor("DP-1", 0, 0, 2560, 1440),
+        make_monitor("HDMI-A-1", 2560, 0, 1920, 1080),
+    ];
+    let view = GridView::new(&monitors, Rect::new(0, 0, 102, 42));
+    assert_eq!(view.inner, Rect::new(1, 1, 100, 40));
+
+    let left = view.monitor_rect(&monitors[0]).unwrap();
+    let right = view.monitor_rect(&monitors[1]).unwrap();
+    assert_eq!(
+        monitor_at(&monitors, &view, left.x + 1, left.y + 1),
+        Some(0)
+    );
+    assert_eq!(
+        monitor_at(&monitors, &view, right.x + 1, right.y + 1),
+        Some(1)
+    );
+    assert_eq!(monitor_at(&monitors, &view, right.x + 1, 40), None);
+
+    // Mapping a box origin back to layout pixels lands within one cell of the monitor origin.
+    let (x, y) = view.to_layout(right.x, right.y);
+    assert!((x - 2560).abs() <= (1.0 / view.scale) as i32);
+    assert_eq!(y, 0);
+}
+
+#[test]
+fn test_mirror_is_stacked_on_its_source() {
+    let mut mirror = make_monitor("HDMI-A-1", 5000, 3000, 1920, 1080);
+    mirror.mirror_of = Some("DP-1".to_string());
+    let monitors = vec![make_monitor("DP-1", 0, 0, 2560, 1440), mirror];
+    let view = GridView::new(&monitors, Rect::new(0, 0, 102, 42));
+
+    // The mirror's own position is ignored: the grid fits the source alone.
+    let source = view.box_rect(&monitors, 0).unwrap();
+    assert_eq!(source.x, 1);
+    let stacked = view.box_rect(&monitors, 1).unwrap();
+    assert_eq!((stacked.x, stacked.y), (source.x + 1, source.y + 1));
+    assert_eq!(monitor_at(&monitors, &view, source.x, source.y), Some(0));
+    assert_eq!(
+        monitor_at(&monitors, &view, stacked.x + 1, stacked.y + 1),
+        Some(1)
+    );
+}
+
+#[test]
+fn test_reserved_strips_inside_box() {
+    let mut m = make_monitor("DP-1", 0, 0, 2000, 1000);
+    m.reserved = Some(Reserved {
+        top: 100,
+        bottom: 0,
+        left: 0,
+        right: 1,
+    });
+    let monitors = vec![m];
+    let view = GridView::new(&monitors, Rect::new(0, 0, 42, 22));
+    assert_eq!(view.scale, 0.02);
+    let inner_box = Rect::new(1, 1, 38, 18);
+    // 100 px at 0.02 cells/px is two rows; 1 px still gets one column.
+    assert_eq!(
+        view.reserved_strips(&monitors[0], inner_box),
+        vec![Rect::new(1, 1, 38, 2), Rect::new(38, 3, 1, 16)]
+    );
+}
+
+#[test]
+fn test_scaled_and_rotated_monitors_use_logical_size() {
+    // A 4K monitor at scale 2 is 1920 logical pixels wide: flush with a 1080p neighbour.
+    let mut uhd = make_monitor("DP-1", 0, 0, 3840, 2160);
+    uhd.scale = 2.0;
+    let monitors = vec![uhd, make_monitor("HDMI-A-1", 1925, 0, 1920, 1080)];
+    assert_eq!(
+        snap_to_neighbours(&monitors, 1, 10),
+        Position { x: 1920, y: 0 }
+    );
+    assert!(!overlaps(
+        &monitors[0],
+        &make_monitor("HDMI-A-1", 1920, 0, 1920, 1080)
+    ));
+    assert!(overlaps(
+        &monitors[0],
+        &make_monitor("HDMI-A-1", 1900, 0, 1920, 1080)
+    ));
+
+    // No gap in the grid: the right box starts where the left one ends.
+    let view = GridView::new(&monitors, Rect::new(0, 0, 102, 42));
+    let left = view.monitor_rect(&monitors[0]).unwrap();
+    let right = view.monitor_rect(&monitors[1]).unwrap();
+    assert!(right.x <= left.right() + 1);
+    assert_eq!(left.height, right.height);
+
+    let mut portrait = make_monitor("DP-2", 0, 0, 2560, 1440);
+    portrait.transform = Transform::Rotate90;
+    let landscape = make_monitor("DP-3", 1440, 0, 2560, 1440);
+    assert!(!overlaps(&portrait, &landscape));
+}
+
+#[test]
+fn test_validate_layout_accepts_touching_monitors() {
+    let mut tv = make_monitor("HDMI-A-1", 5000, 5000, 1920, 1080);
+    tv.mirror_of = Some("DP-1".to_string());
+    let monitors = vec![
+        make_monitor("DP-1", 0, 0, 2560, 1440),
+        make_monitor("DP-2", 2560, 200, 1920, 1080),
+        make_monitor("eDP-1", 320, 1440, 1920, 1200),
+        tv,
+    ];
+    assert_eq!(validate_layout(&monitors), vec![]);
+}
+
+#[test]
+fn test_validate_layout_reports_issues() {
+    let monitors = vec![
+        make_monitor("DP-1", 0, 0, 2560, 1440),
+        make_monitor("DP-2", 2500, 0, 1920, 1080),
+        make_monitor("eDP-1", 0, 1500, 1920, 1200),
+        make_monitor("HDMI-A-1", -3000, -3000, 1920, 1080),
+    ];
+    let issues = validate_layout(&monitors);
+    let kinds: Vec<(Severity, LayoutIssueKind, String)> = issues
+        .iter()
+        .map(|i| (i.severity, i.kind.clone(), i.monitors.join(",")))
+        .collect();
+    assert_eq!(
+        kinds,
+        vec![
+            (
+                Severity::Error,
+                LayoutIssueKind::Overlap,
+                "DP-1,DP-2".to_string()
+            ),
+            (
+                Severity::Warning,
+                LayoutIssueKind::NegativePosition,
+                "HDMI-A-1".to_string()
+            ),
+            (
+                Severity::Warning,
+                LayoutIssueKind::Gap(60),
+                "DP-1,eDP-1".to_string()
+            ),
+            (
+                Severity::Warning,
+                LayoutIssueKind::Island,
+                "eDP-1".to_string()
+            ),
+            (
+                Severity::Warning,
+                LayoutIssueKind::Island,
+                "HDMI-A-1".to_string()
+            ),
+        ]
+    );
+    assert_eq!(issues[2].to_string(), "60px gap between DP-1, eDP-1");
+
+    let mut off = make_monitor("DP-1", 0, 0, 2560, 1440);
+    off.enabled = false;
+    let issues = validate_layout(&[off]);
+    assert_eq!(issues[0].kind, LayoutIssueKind::AllDisabled);
+    assert_eq!(issues[0].severity, Severity::Error);
+}
+
+fn positions(monitors: &[Monitor]) -> Vec<(i32, i32)> {
+    monitors
+        .iter()
+        .map(|m| (m.position.x, m.position.y))
+        .collect()
+}
+
+/// eDP-1 at 1.5x (1280x800 logical), DP-1 rotated (1440x2560), HDMI-A-1 disabled.
+fn mixed_monitors() -> Vec<Monitor> {
+    let mut laptop = make_monitor("eDP-1", 5000, 3000, 1920, 1200);
+    laptop.scale = 1.5;
+    let mut portrait = make_monitor("DP-1", 100, 40, 2560, 1440);
+    portrait.transform = Transform::Rotate90;
+    let mut off = make_monitor("HDMI-A-1", 9000, 0, 1920, 1080);
+    off.enabled = false;
+    vec![
+        laptop,
+        portrait,
+        make_monitor("DP-2", 2000, 0, 1920, 1080),
+        off,
+    ]
+}
+
+#[test]
+fn test_arrange_row_and_column_use_logical_sizes() {
+    let mut monitors = mixed_monitors();
+    arrange(&mut monitors, Arrangement::RowTop).unwrap();
+    assert_eq!(
+        positions(&monitors),
+        [(3360, 0), (0, 0), (1440, 0), (9000, 0)]
+    );
+    assert!(validate_layout(&monitors).is_empty());
+
+    let mut monitors = mixed_monitors();
+    arrange(&mut monitors, Arrangement::RowCentered).unwrap();
+    assert_eq!(
+        positions(&monitors),
+        [(3360, 880), (0, 0), (1440, 740), (9000, 0)]
+    );
+    assert!(validate_layout(&monitors).is_empty());
+
+    let mut monitors = mixed_monitors();
+    arrange(&mut monitors, Arrangement::Column).unwrap();
+    assert_eq!(
+        positions(&monitors),
+        [(320, 3640), (240, 1080), (0, 0), (9000, 0)]
+    );
+    assert!(validate_layout(&monitors).is_empty());
+}
+
+#[test]
+fn test_arrange_l_shape_centres_laptop_below() {
+    let mut monitors = mixed_monitors();
+    monitors[0].position_spec = Some(PositionSpec::Auto);
+    arrange(&mut monitors, Arrangement::LShape).unwrap();
+    assert_eq!(
+        positions(&monitors),
+        [(1040, 2560), (0, 0), (1440, 0), (9000, 0)]
+    );
+    assert_eq!(monitors[0].position_spec, None);
+    assert!(validate_layout(&monitors).is_empty());
+
+    let mut externals = vec![make_monitor("DP-1", 0, 0, 1920, 1080)];
+    assert!(arrange(&mut externals, Arrangement::LShape).is_err());
+    assert_eq!(positions(&externals), [(0, 0)]);
+}
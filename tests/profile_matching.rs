@@ -0,0 +1,210 @@
+//! Profile-to-monitor matching tests.
+
+mod common;
+
+use hypr_monitor_tui::config::Profile;
+use hypr_monitor_tui::hyprland::{Mode, ModeSpec, Monitor, Position, PositionSpec, Resolution};
+
+fn make_monitor(name: &str, description: &str, x: i32) -> Monitor {
+    let mut m = common::monitor(name, x, 0, 2560, 1440);
+    m.description = description.to_string();
+    m
+}
+
+#[test]
+fn test_match_by_description_survives_port_swap() {
+    let saved = vec![
+        make_monitor("DP-1", "Dell Inc. DELL U2720Q AAA", 0),
+        make_monitor("DP-2", "LG Electronics 27GL850 BBB", 2560),
+    ];
+    let profile = Profile::from_monitors("dock".to_string(), None, &saved);
+
+    // The dock enumerated the ports the other way round.
+    let current = vec![
+        make_monitor("DP-1", "LG Electronics 27GL850 BBB", 0),
+        make_monitor("DP-2", "Dell Inc. DELL U2720Q AAA", 0),
+    ];
+    let matched = profile.match_monitors(&current);
+    assert!(matched.unmatched.is_empty());
+    assert_eq!(matched.monitors[0].name, "DP-2");
+    assert_eq!(matched.monitors[0].position.x, 0);
+    assert_eq!(matched.monitors[1].name, "DP-1");
+    assert_eq!(matched.monitors[1].position.x, 2560);
+}
+
+#[test]
+fn test_match_description_by_prefix_prefers_longest() {
+    let mut profile = Profile::from_monitors(
+        "imported".to_string(),
+        None,
+        &[
+            make_monitor("DP-1", "Dell Inc. DELL U2720Q", 0),
+            make_monitor("DP-2", "Dell Inc. DELL U2720Q 7QK1234", 2560),
+        ],
+    );
+    // Imported `desc:` rules carry no connector name.
+    for mc in profile.monitors.iter_mut() {
+        mc.name.clear();
+    }
+
+    let current = vec![
+        make_monitor("DP-3", "Dell Inc. DELL U2720Q 7QK1234", 0),
+        make_monitor("DP-4", "Dell Inc. DELL U2720Q 9XY5678", 0),
+    ];
+    let matched = profile.match_monitors(&current);
+    assert!(matched.unmatched.is_empty());
+    assert_eq!(matched.monitors[0].name, "DP-4");
+    assert_eq!(matched.monitors[1].name, "DP-3");
+    assert_eq!(matched.monitors[1].position.x, 2560);
+}
+
+#[test]
+fn test_match_falls_back_to_name_and_reports_missing() {
+    let mut profile = Profile::from_monitors(
+        "home".to_string(),
+        None,
+        &[
+            make_monitor("eDP-1", "", 0),
+            make_monitor("HDMI-A-1", "Samsung TV", 1920),
+        ],
+    );
+    profile.monitors[0].description = None;
+
+    let current = vec![make_monitor("eDP-1", "BOE 0x095F", 0)];
+    let matched = profile.match_monitors(&current);
+    assert_eq!(matched.monitors.len(), 1);
+    assert_eq!(matched.monitors[0].name, "eDP-1");
+    assert_eq!(matched.unmatched, ["Samsung TV"]);
+}
+
+#[test]
+fn test_description_roundtrips_through_toml() {
+    let profile = Profile::from_monitors(
+        "p".to_string(),
+        None,
+        &[make_monitor("DP-1", "Dell Inc. DELL U2720Q AAA", 0)],
+    );
+    let s = toml::to_string_pretty(&profile).unwrap();
+    let back: Profile = toml::from_str(&s).unwrap();
+    assert_eq!(
+        back.monitors[0].description.as_deref(),
+        Some("Dell Inc. DELL U2720Q AAA")
+    );
+}
+
+#[test]
+fn test_symbolic_mode_and_position_resolve_against_live_layout() {
+    let profile: Profile = toml::from_str(
+        r#"
+name = "desk"
+
+[[monitors]]
+name = "DP-1"
+position = [0, 0]
+
+[[monitors]]
+name = "DP-2"
+resolution = "highrr"
+position = "auto-right"
+"#,
+    )
+    .unwrap();
+    let mut dp2 = make_monitor("DP-2", "", 0);
+    dp2.available_modes = vec![
+        Mode {
+            width: 2560,
+            height: 1440,
+            refresh_rate: 60.0,
+        },
+        Mode {
+            width: 1920,
+            height: 1080,
+            refresh_rate: 144.0,
+        },
+    ];
+    let matched = profile.match_monitors(&[make_monitor("DP-1", "", 0), dp2]);
+
+    let m = &matched.monitors[1];
+    assert_eq!(m.mode_spec, Some(ModeSpec::HighRr));
+    assert_eq!(
+        m.resolution,
+        Resolution {
+            width: 1920,
+            height: 1080
+        }
+    );
+    assert_eq!(m.position_spec, Some(PositionSpec::AutoRight));
+    assert_eq!(m.position, Position { x: 2560, y: 0 });
+
+    // Saving and exporting keep the symbolic values.
+    let saved = Profile::from_monitors("desk".to_string(), None, &matched.monitors);
+    let s = toml::to_string_pretty(&saved).unwrap();
+    assert!(s.contains("position = \"auto-right\""));
+    assert!(s.contains("resolution = \"highrr\""));
+    assert_eq!(
+        hypr_monitor_tui::hyprland::monitor_rule(m),
+        "DP-2,highrr,auto-right,1"
+    );
+}
+
+#[test]
+fn test_resolution_without_refresh_rate_takes_highest_rate() {
+    let profile: Profile = toml::from_str(
+        r#"
+name = "desk"
+
+[[monitors]]
+name = "DP-1"
+resolution = "2560x1440"
+position = [0, 0]
+"#,
+    )
+    .unwrap();
+    let mut dp1 = make_monitor("DP-1", "", 0);
+    dp1.available_modes = [
+        (2560, 1440, 60.0),
+        (2560, 1440, 143.97),
+        (1920, 1080, 240.0),
+    ]
+    .into_iter()
+    .map(|(width, height, refresh_rate)| Mode {
+        width,
+        height,
+        refresh_rate,
+    })
+    .collect();
+    let matched = profile.match_monitors(&[dp1]);
+
+    let m = &matched.monitors[0];
+    assert!((m.refresh_rate - 143.97).abs() < 0.001);
+    // Hyprland picks the rate itself, so it stays unset when saving and exporting.
+    let saved = Profile::from_monitors("desk".to_string(), None, &matched.monitors);
+    assert_eq!(saved.monitors[0].resolution.as_deref(), Some("2560x1440"));
+    assert_eq!(saved.monitors[0].refresh_rate, None);
+    assert_eq!(
+        hypr_monitor_tui::hyprland::monitor_rule(m),
+        "DP-1,2560x1440,0x0,1"
+    );
+}
+
+#[test]
+fn test_mirror_source_follows_port_swap() {
+    let mut projector = make_monitor("DP-2", "Epson Projector", 0);
+    projector.mirror_of = Some("DP-1".to_string());
+    let profile = Profile::from_monitors(
+        "present".to_string(),
+        None,
+        &[
+            make_monitor("DP-1", "Dell Inc. DELL U2720Q AAA", 0),
+            projector,
+        ],
+    );
+
+    let current = vec![
+        make_monitor("DP-1", "Epson Projector", 0),
+        make_monitor("DP-2", "Dell Inc. DELL U2720Q AAA", 0),
+    ];
+    let matched = profile.match_monitors(&current);
+    assert_eq!(matched.monitors[1].name, "DP-1");
+    assert_eq!(matched.monitors[1].mirror_of.as_deref(), Some("DP-2"));
+}